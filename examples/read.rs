@@ -17,4 +17,15 @@ fn main() {
     println!("    field_length: {}", entry_map.field_length());
     println!("    field_position: {}", entry_map.field_position());
     println!("    field_tag: {}", entry_map.field_tag());
+
+    println!("\nDDR Fields");
+    for ddf in ddr.data_descriptive_fields() {
+        let field_controls = ddf.field_controls();
+        println!("  {}: {}", ddf.tag(), ddf.field_name());
+        println!("    data_structure: {:?}", field_controls.data_structure());
+        println!("    data_type: {:?}", field_controls.data_type());
+        println!("    lexical_level: {:?}", field_controls.escape_sequence());
+        println!("    array_descriptor: {}", ddf.array_descriptor());
+        println!("    format_controls: {}", ddf.format_controls());
+    }
 }
@@ -1,30 +1,127 @@
-use crate::{ddr::DataDescriptiveRecord, dr::DataRecord, ReadResult, Reader};
+use crate::{
+    ddr::DataDescriptiveRecord,
+    dr::{DataRecord, RecordIdentifier},
+    DataDescriptiveFileReader, ReadResult, Reader, ReaderOptions,
+};
 
+#[cfg(feature = "mmap")]
+use crate::DataDescriptiveFileView;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{Cursor, Read};
 use std::path::Path;
 
 pub struct DataDescriptiveFile {
     data_descriptive_record: DataDescriptiveRecord,
     data_records: Vec<DataRecord>,
+    /// position of each record by its record identifier
+    record_index: HashMap<RecordIdentifier, usize>,
 }
 
 impl DataDescriptiveFile {
+    /// Reads the data descriptive record of the file, leaving the data records to be read one
+    /// at a time
+    pub fn open<P: AsRef<Path>>(path: P) -> ReadResult<DataDescriptiveFileReader<File>> {
+        DataDescriptiveFile::open_with_options(path, &ReaderOptions::default())
+    }
+
+    pub fn open_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveFileReader<File>> {
+        let file = File::open(path.as_ref())?;
+        DataDescriptiveFile::open_reader_with_options(file, options)
+    }
+
+    /// Reads the data descriptive record from any source, such as standard input or a
+    /// decompressor, leaving the data records to be read one at a time
+    pub fn open_reader<R: Read>(reader: R) -> ReadResult<DataDescriptiveFileReader<R>> {
+        DataDescriptiveFile::open_reader_with_options(reader, &ReaderOptions::default())
+    }
+
+    pub fn open_reader_with_options<R: Read>(
+        reader: R,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveFileReader<R>> {
+        DataDescriptiveFileReader::new(Reader::new(reader), options)
+    }
+
+    /// Maps the file into memory and reads its data descriptive record, the data records are
+    /// then read as views into the mapped bytes
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or written to, by this or any other process, while the
+    /// view is alive. The mapped bytes would change under the borrowed records, and reading
+    /// past a truncated end faults.
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map<P: AsRef<Path>>(path: P) -> ReadResult<DataDescriptiveFileView<Mmap>> {
+        DataDescriptiveFile::map_with_options(path, &ReaderOptions::default())
+    }
+
+    /// # Safety
+    ///
+    /// The same as [`DataDescriptiveFile::map`].
+    #[cfg(feature = "mmap")]
+    pub unsafe fn map_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveFileView<Mmap>> {
+        let file = File::open(path.as_ref())?;
+        let mmap = Mmap::map(&file)?;
+        DataDescriptiveFileView::new(mmap, options)
+    }
+
+    /// Reads the whole file, resolving the recursive tree links between its records
     pub fn read<P: AsRef<Path>>(path: P) -> ReadResult<DataDescriptiveFile> {
+        DataDescriptiveFile::read_with_options(path, &ReaderOptions::default())
+    }
+
+    pub fn read_with_options<P: AsRef<Path>>(
+        path: P,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveFile> {
         let file = File::open(path.as_ref())?;
-        let buffer = BufReader::new(Box::new(file));
-        let mut reader = Reader::new(buffer);
+        DataDescriptiveFile::from_reader_with_options(file, options)
+    }
 
-        let data_descriptive_record = DataDescriptiveRecord::read(&mut reader)?;
+    /// Reads a whole file held in memory
+    pub fn from_bytes(bytes: &[u8]) -> ReadResult<DataDescriptiveFile> {
+        DataDescriptiveFile::from_reader(Cursor::new(bytes))
+    }
 
-        let mut data_records = Vec::new();
-        while !reader.is_eof()? {
-            let data_record = DataRecord::read(&mut reader)?;
-            data_records.push(data_record);
+    /// Reads a whole file from any source
+    pub fn from_reader<R: Read>(reader: R) -> ReadResult<DataDescriptiveFile> {
+        DataDescriptiveFile::from_reader_with_options(reader, &ReaderOptions::default())
+    }
+
+    pub fn from_reader_with_options<R: Read>(
+        reader: R,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveFile> {
+        let mut records = DataDescriptiveFile::open_reader_with_options(reader, options)?.records();
+        let mut data_records: Vec<DataRecord> = Vec::new();
+        for data_record in records.by_ref() {
+            data_records.push(data_record?);
         }
+        let data_descriptive_record = records.into_data_descriptive_record();
+
+        let mut record_index: HashMap<RecordIdentifier, usize> = HashMap::new();
+        for (index, data_record) in data_records.iter().enumerate() {
+            if let Some(record_identifier) = data_record.record_identifier() {
+                record_index
+                    .entry(record_identifier.clone())
+                    .or_insert(index);
+            }
+        }
+
         Ok(DataDescriptiveFile {
             data_descriptive_record,
             data_records,
+            record_index,
         })
     }
 
@@ -35,4 +132,125 @@ impl DataDescriptiveFile {
     pub fn data_records(&self) -> &Vec<DataRecord> {
         &self.data_records
     }
+
+    /// The record with the given identifier
+    pub fn data_record(&self, record_identifier: &RecordIdentifier) -> Option<&DataRecord> {
+        self.record_index
+            .get(record_identifier)
+            .map(|index| &self.data_records[*index])
+    }
+
+    /// The records linked from the recursive tree link fields of the record, links to records
+    /// that are not in the file are skipped
+    pub fn linked_records(&self, data_record: &DataRecord) -> Vec<&DataRecord> {
+        data_record
+            .tree_links()
+            .iter()
+            .filter_map(|link| self.data_record(link))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{dr::RecordIdentifier, DataDescriptiveFile, FIELD_TERMINATOR, UNIT_TERMINATOR};
+
+    /// A file with three data records, the first linked to the records in `links`
+    fn control_field_records(links: &str) -> Vec<u8> {
+        [
+            "002523LE1 0900080 ! 3404".as_bytes(),
+            "000002700000001035002700020380062TEST039010000030330139".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001TEST00010003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0100;&   Record identifier".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR],
+            "(I(3))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   Test file for control fields".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1600;&   Test field".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "NAME!VALU".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A,I(2))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1100;&   Tree links".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "*RCID".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(I(3))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            // the directory leaves room for two links of three digits
+            "00078 D     00058   3404".as_bytes(),
+            "00010040000TEST009000400030070013".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "001".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "first".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "12".as_bytes(),
+            &[FIELD_TERMINATOR],
+            links.as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00040 D     00036   3404".as_bytes(),
+            "00010040000".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "002".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00040 D     00036   3404".as_bytes(),
+            "00010040000".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "003".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_linked_records() {
+        let target = DataDescriptiveFile::from_bytes(&control_field_records("002003")).unwrap();
+
+        let description = target
+            .data_descriptive_record()
+            .user_augmented_file_description()
+            .unwrap();
+        assert_eq!(description.description(), "Test file for control fields");
+        assert!(target
+            .data_descriptive_record()
+            .data_descriptive_field("0002")
+            .is_none());
+
+        let data_record = &target.data_records()[0];
+        assert_eq!(
+            data_record.tree_links(),
+            &vec![RecordIdentifier::Number(2), RecordIdentifier::Number(3)]
+        );
+        let linked: Vec<&RecordIdentifier> = target
+            .linked_records(data_record)
+            .iter()
+            .filter_map(|dr| dr.record_identifier())
+            .collect();
+        assert_eq!(
+            linked,
+            vec![&RecordIdentifier::Number(2), &RecordIdentifier::Number(3)]
+        );
+
+        // a link to a record that is not in the file is read, but resolves to nothing
+        let target = DataDescriptiveFile::from_bytes(&control_field_records("002004")).unwrap();
+        let data_record = &target.data_records()[0];
+        assert_eq!(
+            data_record.tree_links(),
+            &vec![RecordIdentifier::Number(2), RecordIdentifier::Number(4)]
+        );
+        assert!(target.data_record(&RecordIdentifier::Number(4)).is_none());
+        let linked: Vec<&RecordIdentifier> = target
+            .linked_records(data_record)
+            .iter()
+            .filter_map(|dr| dr.record_identifier())
+            .collect();
+        assert_eq!(linked, vec![&RecordIdentifier::Number(2)]);
+    }
 }
@@ -0,0 +1,397 @@
+use crate::{ddr::FormatControls, error::ReadError, ReadResult};
+use std::fmt;
+
+/// vector label delimiter
+const VECTOR_DELIMITER: char = '!';
+
+/// repeating part indicator
+const REPEAT_INDICATOR: char = '*';
+
+/// Cartesian label delimiter
+const CARTESIAN_DELIMITER: &str = "\\\\";
+
+/// concatenated structure delimiter, only used for fields of concatenated structure. It is
+/// twice the Cartesian label delimiter so that a sub-structure may have Cartesian labels.
+const CONCATENATION_DELIMITER: &str = "\\\\\\\\";
+
+/// The parsed array descriptor of a data descriptive field
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArrayDescriptor {
+    /// No array descriptor, the field holds a single data item
+    Elementary,
+    /// Vector labels such as `RCNM!RCID`, `*YCOO!XCOO` or `STPT!CTPT!ENPT*YCOO!XCOO`
+    Vector {
+        labels: Vec<String>,
+        repeat_start: Option<usize>,
+    },
+    /// Cartesian labels such as `ROW1!ROW2\\COL1!COL2`, one label vector per dimension
+    Cartesian {
+        dimensions: Vec<Vec<String>>,
+        repeating: bool,
+    },
+    /// Numeric descriptor such as `2,3,4`: the number of dimensions followed by their extents
+    Numeric {
+        extents: Vec<usize>,
+        repeating: bool,
+    },
+    /// Sub-structures of a concatenated field such as `RCNM!RCID\\\\*ATTL!ATVL`, only the last
+    /// one may repeat
+    Concatenated {
+        parts: Vec<ArrayDescriptor>,
+        /// the labels of all sub-structures
+        labels: Vec<String>,
+    },
+}
+
+impl ArrayDescriptor {
+    pub fn parse(value: &str) -> ReadResult<ArrayDescriptor> {
+        if value.is_empty() {
+            return Ok(ArrayDescriptor::Elementary);
+        }
+
+        let repeating = value.starts_with(REPEAT_INDICATOR);
+        let body = if repeating { &value[1..] } else { value };
+
+        if !body.is_empty() && body.chars().all(|c| c.is_ascii_digit() || c == ',') {
+            return ArrayDescriptor::parse_numeric(value, body, repeating);
+        }
+
+        if body.contains(CARTESIAN_DELIMITER) {
+            let mut dimensions: Vec<Vec<String>> = Vec::new();
+            for dimension in body.split(CARTESIAN_DELIMITER) {
+                dimensions.push(ArrayDescriptor::parse_labels(value, dimension)?);
+            }
+            return Ok(ArrayDescriptor::Cartesian {
+                dimensions,
+                repeating,
+            });
+        }
+
+        let mut parts = value.split(REPEAT_INDICATOR);
+        let prefix = parts.next().unwrap_or_default();
+        let repeated = parts.next();
+        if parts.next().is_some() {
+            return Err(ArrayDescriptor::error(
+                value,
+                "more than one repeating part",
+            ));
+        }
+
+        let mut labels = if prefix.is_empty() {
+            Vec::new()
+        } else {
+            ArrayDescriptor::parse_labels(value, prefix)?
+        };
+        let repeat_start = match repeated {
+            Some(repeated) => {
+                let start = labels.len();
+                labels.extend(ArrayDescriptor::parse_labels(value, repeated)?);
+                Some(start)
+            }
+            None => None,
+        };
+
+        Ok(ArrayDescriptor::Vector {
+            labels,
+            repeat_start,
+        })
+    }
+
+    /// Parses the descriptor of a field of concatenated structure, where each sub-structure has
+    /// its own part of the descriptor
+    pub fn parse_concatenated(value: &str) -> ReadResult<ArrayDescriptor> {
+        if value.is_empty() {
+            return Ok(ArrayDescriptor::Elementary);
+        }
+
+        let mut parts: Vec<ArrayDescriptor> = Vec::new();
+        for part in value.split(CONCATENATION_DELIMITER) {
+            if part.is_empty() {
+                return Err(ArrayDescriptor::error(value, "empty sub-structure"));
+            }
+            if parts.last().map_or(false, |p| p.repeat_start().is_some()) {
+                return Err(ArrayDescriptor::error(
+                    value,
+                    "only the last sub-structure may repeat",
+                ));
+            }
+            parts.push(ArrayDescriptor::parse(part)?);
+        }
+        let labels = parts
+            .iter()
+            .flat_map(|part| part.labels().iter().cloned())
+            .collect();
+        Ok(ArrayDescriptor::Concatenated { parts, labels })
+    }
+
+    fn parse_numeric(value: &str, body: &str, repeating: bool) -> ReadResult<ArrayDescriptor> {
+        let mut numbers: Vec<usize> = Vec::new();
+        for number in body.split(',') {
+            match number.parse::<usize>() {
+                Ok(number) if number > 0 => numbers.push(number),
+                _ => return Err(ArrayDescriptor::error(value, "invalid dimension extent")),
+            }
+        }
+        let extents = numbers.split_off(1);
+        if numbers[0] != extents.len() {
+            return Err(ArrayDescriptor::error(
+                value,
+                &format!(
+                    "{} dimensions declared but {} extents given",
+                    numbers[0],
+                    extents.len()
+                ),
+            ));
+        }
+        Ok(ArrayDescriptor::Numeric { extents, repeating })
+    }
+
+    fn parse_labels(value: &str, labels: &str) -> ReadResult<Vec<String>> {
+        labels
+            .split(VECTOR_DELIMITER)
+            .map(|label| {
+                if label.is_empty()
+                    || label
+                        .chars()
+                        .any(|c| c.is_control() || c.is_whitespace() || c == REPEAT_INDICATOR)
+                {
+                    Err(ArrayDescriptor::error(
+                        value,
+                        &format!("invalid label '{}'", label),
+                    ))
+                } else {
+                    Ok(String::from(label))
+                }
+            })
+            .collect()
+    }
+
+    fn error(value: &str, reason: &str) -> ReadError {
+        ReadError::ParseError(format!("Invalid Array Descriptor: {} ({})", value, reason))
+    }
+
+    /// The subfield labels, for Cartesian labels those of the last dimension and for
+    /// concatenated structures those of all sub-structures
+    pub fn labels(&self) -> &[String] {
+        match self {
+            ArrayDescriptor::Vector { labels, .. }
+            | ArrayDescriptor::Concatenated { labels, .. } => labels,
+            ArrayDescriptor::Cartesian { dimensions, .. } => {
+                dimensions.last().map_or(&[], |labels| labels.as_slice())
+            }
+            _ => &[],
+        }
+    }
+
+    /// Index of the first label of the repeating part, if any
+    pub fn repeat_start(&self) -> Option<usize> {
+        match self {
+            ArrayDescriptor::Vector { repeat_start, .. } => *repeat_start,
+            ArrayDescriptor::Cartesian { repeating, .. }
+            | ArrayDescriptor::Numeric { repeating, .. } => {
+                if *repeating {
+                    Some(0)
+                } else {
+                    None
+                }
+            }
+            ArrayDescriptor::Concatenated { parts, .. } => {
+                let (last, others) = parts.split_last()?;
+                let start = last.repeat_start()?;
+                Some(
+                    others
+                        .iter()
+                        .map(|part| part.subfield_count())
+                        .sum::<usize>()
+                        + start,
+                )
+            }
+            ArrayDescriptor::Elementary => None,
+        }
+    }
+
+    /// The extent of each dimension of an array
+    pub fn shape(&self) -> Option<Vec<usize>> {
+        match self {
+            ArrayDescriptor::Cartesian { dimensions, .. } => {
+                Some(dimensions.iter().map(|labels| labels.len()).collect())
+            }
+            ArrayDescriptor::Numeric { extents, .. } => Some(extents.clone()),
+            _ => None,
+        }
+    }
+
+    /// The number of subfields described, for repeating descriptors a single repetition
+    pub fn subfield_count(&self) -> usize {
+        match self {
+            ArrayDescriptor::Elementary => 1,
+            ArrayDescriptor::Vector { labels, .. } => labels.len(),
+            ArrayDescriptor::Concatenated { parts, .. } => {
+                parts.iter().map(|part| part.subfield_count()).sum()
+            }
+            _ => self.shape().map_or(0, |shape| shape.iter().product()),
+        }
+    }
+
+    /// Checks that the format controls do not describe more subfields than this descriptor
+    pub fn validate(&self, format_controls: &FormatControls) -> ReadResult<()> {
+        let formats = format_controls.formats().len();
+        let subfields = self.subfield_count();
+        if formats > subfields {
+            return Err(ReadError::ParseError(format!(
+                "Format Controls {} describe {} subfields but Array Descriptor {} has {}",
+                format_controls, formats, self, subfields
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ArrayDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArrayDescriptor::Elementary => Ok(()),
+            ArrayDescriptor::Vector {
+                labels,
+                repeat_start,
+            } => {
+                for (i, label) in labels.iter().enumerate() {
+                    if Some(i) == *repeat_start {
+                        write!(f, "{}", REPEAT_INDICATOR)?;
+                    } else if i > 0 {
+                        write!(f, "{}", VECTOR_DELIMITER)?;
+                    }
+                    write!(f, "{}", label)?;
+                }
+                Ok(())
+            }
+            ArrayDescriptor::Cartesian {
+                dimensions,
+                repeating,
+            } => {
+                if *repeating {
+                    write!(f, "{}", REPEAT_INDICATOR)?;
+                }
+                let dimensions: Vec<String> = dimensions
+                    .iter()
+                    .map(|labels| labels.join(&VECTOR_DELIMITER.to_string()))
+                    .collect();
+                write!(f, "{}", dimensions.join(CARTESIAN_DELIMITER))
+            }
+            ArrayDescriptor::Numeric { extents, repeating } => {
+                if *repeating {
+                    write!(f, "{}", REPEAT_INDICATOR)?;
+                }
+                write!(f, "{}", extents.len())?;
+                for extent in extents {
+                    write!(f, ",{}", extent)?;
+                }
+                Ok(())
+            }
+            ArrayDescriptor::Concatenated { parts, .. } => {
+                for (i, part) in parts.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{}", CONCATENATION_DELIMITER)?;
+                    }
+                    write!(f, "{}", part)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::ddr::{ArrayDescriptor, FormatControls};
+
+    #[test]
+    fn test_vector_labels() {
+        let target = ArrayDescriptor::parse("RCNM!RCID!PRIM!GRUP!OBJL!RVER!RUIN").unwrap();
+        assert_eq!(target.labels().len(), 7);
+        assert_eq!(target.labels()[4], "OBJL");
+        assert_eq!(target.repeat_start(), None);
+
+        let target = ArrayDescriptor::parse("*YCOO!XCOO").unwrap();
+        assert_eq!(target.labels(), &["YCOO", "XCOO"]);
+        assert_eq!(target.repeat_start(), Some(0));
+
+        let target = ArrayDescriptor::parse("STPT!CTPT!ENPT*YCOO!XCOO").unwrap();
+        assert_eq!(target.labels().len(), 5);
+        assert_eq!(target.repeat_start(), Some(3));
+        assert_eq!(target.to_string(), "STPT!CTPT!ENPT*YCOO!XCOO");
+    }
+
+    #[test]
+    fn test_cartesian_labels() {
+        let target = ArrayDescriptor::parse("*ROW1!ROW2!ROW3\\\\COL1!COL2").unwrap();
+        assert_eq!(target.shape(), Some(vec![3, 2]));
+        assert_eq!(target.labels(), &["COL1", "COL2"]);
+        assert_eq!(target.repeat_start(), Some(0));
+        assert_eq!(target.subfield_count(), 6);
+        assert_eq!(target.to_string(), "*ROW1!ROW2!ROW3\\\\COL1!COL2");
+    }
+
+    #[test]
+    fn test_numeric_descriptor() {
+        let target = ArrayDescriptor::parse("2,3,4").unwrap();
+        assert_eq!(target.shape(), Some(vec![3, 4]));
+        assert_eq!(target.subfield_count(), 12);
+        assert_eq!(target.to_string(), "2,3,4");
+
+        assert!(ArrayDescriptor::parse("3,3,4").is_err());
+        assert!(ArrayDescriptor::parse("2,0,4").is_err());
+    }
+
+    #[test]
+    fn test_concatenated_descriptor() {
+        let target = ArrayDescriptor::parse_concatenated("RCNM!RCID\\\\\\\\*ATTL!ATVL").unwrap();
+        assert_eq!(target.labels(), &["RCNM", "RCID", "ATTL", "ATVL"]);
+        assert_eq!(target.repeat_start(), Some(2));
+        assert_eq!(target.subfield_count(), 4);
+        assert_eq!(target.to_string(), "RCNM!RCID\\\\\\\\*ATTL!ATVL");
+
+        let target = ArrayDescriptor::parse_concatenated("A!B\\\\\\\\2,2,3").unwrap();
+        assert_eq!(target.subfield_count(), 8);
+        assert_eq!(target.repeat_start(), None);
+
+        // a sub-structure with Cartesian labels
+        let target =
+            ArrayDescriptor::parse_concatenated("A!B\\\\\\\\ROW1!ROW2\\\\COL1!COL2").unwrap();
+        assert_eq!(target.labels(), &["A", "B", "COL1", "COL2"]);
+        assert_eq!(target.subfield_count(), 6);
+        assert_eq!(target.to_string(), "A!B\\\\\\\\ROW1!ROW2\\\\COL1!COL2");
+
+        assert!(ArrayDescriptor::parse_concatenated("*A!B\\\\\\\\C").is_err());
+        assert!(ArrayDescriptor::parse_concatenated("A\\\\\\\\").is_err());
+    }
+
+    #[test]
+    fn test_invalid_array_descriptor() {
+        let values = ["A!!B", "A*B*C", "*", "A!B\\\\", "A B"];
+        for value in &values {
+            assert!(ArrayDescriptor::parse(value).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let target = ArrayDescriptor::parse("STPT!CTPT!ENPT*YCOO!XCOO").unwrap();
+        assert!(target
+            .validate(&FormatControls::parse("(2b24)").unwrap())
+            .is_ok());
+
+        let target = ArrayDescriptor::parse("AGEN!FIDN!FIDS").unwrap();
+        assert!(target
+            .validate(&FormatControls::parse("(b12,b14,b12)").unwrap())
+            .is_ok());
+        assert!(target
+            .validate(&FormatControls::parse("(b12,b14,2b12)").unwrap())
+            .is_err());
+
+        let target = ArrayDescriptor::parse("").unwrap();
+        assert!(target
+            .validate(&FormatControls::parse("(b12)").unwrap())
+            .is_ok());
+    }
+}
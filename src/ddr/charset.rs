@@ -0,0 +1,121 @@
+use crate::{ddr::LexicalLevel, error::ReadError, ReadResult};
+use std::fmt;
+
+/// The coded character set designated by the truncated ISO 2022 escape sequence of the field
+/// controls, e.g. `-A ` for the right half of ISO 8859-1 or `%/G` for UTF-8. The escape
+/// character itself is left out and unused positions are blank.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CharacterSet {
+    intermediates: String,
+    final_byte: Option<char>,
+}
+
+impl CharacterSet {
+    pub fn parse(value: &str) -> ReadResult<CharacterSet> {
+        let sequence = value.trim_end_matches(' ');
+        let mut chars: Vec<char> = sequence.chars().collect();
+        let final_byte = chars.pop();
+
+        // intermediate bytes come from columns 2/0 to 2/15, final bytes from 3/0 to 7/14
+        let valid = chars.iter().all(|c| ('\u{20}'..='\u{2f}').contains(c))
+            && final_byte.map_or(true, |c| ('\u{30}'..='\u{7e}').contains(&c));
+        if !valid {
+            return Err(ReadError::ParseError(format!(
+                "Invalid Truncated Escape Sequence: {}",
+                value
+            )));
+        }
+
+        Ok(CharacterSet {
+            intermediates: chars.into_iter().collect(),
+            final_byte,
+        })
+    }
+
+    /// The intermediate bytes of the escape sequence, selecting the kind of designation
+    pub fn intermediates(&self) -> &String {
+        &self.intermediates
+    }
+
+    /// The final byte of the escape sequence, selecting the character set
+    pub fn final_byte(&self) -> Option<char> {
+        self.final_byte
+    }
+
+    /// Whether no escape sequence is given and the default character set applies
+    pub fn is_default(&self) -> bool {
+        self.final_byte.is_none()
+    }
+
+    /// The lexical level used to decode the character data of the field
+    pub fn lexical_level(&self) -> ReadResult<LexicalLevel> {
+        match (self.intermediates.as_str(), self.final_byte) {
+            ("", None) => Ok(LexicalLevel::Level0),
+            // G1 designation of the right half of ISO 8859-1
+            ("-", Some('A')) => Ok(LexicalLevel::Level1),
+            // UCS-2 of ISO 10646 at implementation levels 1, 2 and 3
+            ("%/", Some('@')) | ("%/", Some('C')) | ("%/", Some('E')) => Ok(LexicalLevel::Level2),
+            // UTF-8, with or without standard return
+            ("%/", Some('G')) | ("%/", Some('H')) | ("%/", Some('I')) | ("%", Some('G')) => {
+                Ok(LexicalLevel::Utf8)
+            }
+            _ => Err(ReadError::UnsupportedEncoding(self.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for CharacterSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sequence = match self.final_byte {
+            Some(final_byte) => format!("{}{}", self.intermediates, final_byte),
+            None => String::new(),
+        };
+        write!(f, "{:<3}", sequence)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        ddr::{CharacterSet, LexicalLevel},
+        error::ReadError,
+    };
+
+    #[test]
+    fn test_character_sets() {
+        let values = ["   ", "-A ", "%/@", "%/G", "%G "];
+        let levels = [
+            LexicalLevel::Level0,
+            LexicalLevel::Level1,
+            LexicalLevel::Level2,
+            LexicalLevel::Utf8,
+            LexicalLevel::Utf8,
+        ];
+
+        for (value, level) in values.iter().zip(levels.iter()) {
+            let target = CharacterSet::parse(value).unwrap();
+            assert_eq!(&target.lexical_level().unwrap(), level);
+            assert_eq!(&target.to_string(), value);
+        }
+
+        let target = CharacterSet::parse("%/G").unwrap();
+        assert_eq!(target.intermediates(), "%/");
+        assert_eq!(target.final_byte(), Some('G'));
+        assert!(CharacterSet::parse("   ").unwrap().is_default());
+    }
+
+    #[test]
+    fn test_unsupported_character_sets() {
+        // ISO 8859-2 and ISO 8859-5 are well formed but not supported
+        for value in &["-B ", "-L "] {
+            match CharacterSet::parse(value).unwrap().lexical_level() {
+                Err(ReadError::UnsupportedEncoding(sequence)) => assert_eq!(&sequence, value),
+                e => panic!("{:?}", e),
+            }
+        }
+
+        for value in &["A- ", "%/\u{7f}", "a b"] {
+            assert!(CharacterSet::parse(value).is_err(), "{}", value);
+        }
+    }
+}
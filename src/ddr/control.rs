@@ -0,0 +1,94 @@
+use crate::{
+    ddr::{FieldControls, Leader},
+    error::ReadError,
+    ReadResult, Reader, UNIT_TERMINATOR,
+};
+use std::io::Read;
+
+/// Tag of the file control field
+pub const FILE_CONTROL_TAG: &str = "0000";
+
+/// Tag of the record identifier field
+pub const RECORD_IDENTIFIER_TAG: &str = "0001";
+
+/// Tag of the user augmented file description field
+pub const USER_AUGMENTED_FILE_DESCRIPTION_TAG: &str = "0002";
+
+/// Tag of the recursive tree link field
+pub const RECURSIVE_TREE_LINK_TAG: &str = "0003";
+
+/// Free text describing the file, supplied by the user in the `0002` field
+#[derive(Debug)]
+pub struct UserAugmentedFileDescription {
+    field_controls: FieldControls,
+    units: Vec<String>,
+}
+
+impl UserAugmentedFileDescription {
+    pub fn read<T: Read>(
+        reader: &mut Reader<T>,
+        leader: &Leader,
+    ) -> ReadResult<UserAugmentedFileDescription> {
+        let field_controls = reader.read_str(*leader.field_control_length() as usize)?;
+        let field_controls = FieldControls::parse(&field_controls)?;
+
+        let text = reader.read_str_ft()?;
+        let mut units: Vec<String> = text
+            .split(UNIT_TERMINATOR as char)
+            .map(String::from)
+            .collect();
+        // a trailing unit terminator does not start another unit
+        if units.len() > 1 && units.last().map(String::as_str) == Some("") {
+            units.pop();
+        }
+        if units.iter().all(String::is_empty) {
+            return Err(ReadError::ParseError(String::from(
+                "Empty User Augmented File Description",
+            )));
+        }
+
+        Ok(UserAugmentedFileDescription {
+            field_controls,
+            units,
+        })
+    }
+
+    pub fn field_controls(&self) -> &FieldControls {
+        &self.field_controls
+    }
+
+    /// The units of the description, as separated by unit terminators
+    pub fn units(&self) -> &Vec<String> {
+        &self.units
+    }
+
+    /// The description with its units on separate lines
+    pub fn description(&self) -> String {
+        self.units.join("\n")
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::leader::tests::ascii_ddr_leader;
+    use crate::{ddr::UserAugmentedFileDescription, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_user_augmented_file_description() {
+        let leader = ascii_ddr_leader().unwrap();
+        let bytes = [
+            "0000;&   ".as_bytes(),
+            "Produced for testing".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "Second unit".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let target = UserAugmentedFileDescription::read(&mut reader, &leader).unwrap();
+
+        assert_eq!(target.units(), &vec!["Produced for testing", "Second unit"]);
+        assert_eq!(target.description(), "Produced for testing\nSecond unit");
+    }
+}
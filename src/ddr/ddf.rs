@@ -1,21 +1,27 @@
-use crate::{ddr::DirectoryEntry, error::ReadError, ReadResult, Reader};
-use std::io::{Read, Seek};
+use crate::{
+    ddr::{ArrayDescriptor, CharacterSet, DirectoryEntry, FormatCode, FormatControls, Leader},
+    error::ReadError,
+    reader::parse_str,
+    ReadResult, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR,
+};
+use std::borrow::Cow;
+use std::io::Read;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum DataStructure {
     SingleDataItem,
     LinearStructure,
     MultiDimensionalStructure,
-    Unknown3,
+    ConcatenatedStructure,
 }
 
 impl DataStructure {
-    fn from_char(value: char) -> ReadResult<DataStructure> {
+    pub(crate) fn from_char(value: char) -> ReadResult<DataStructure> {
         match value {
             '0' => Ok(DataStructure::SingleDataItem),
             '1' => Ok(DataStructure::LinearStructure),
             '2' => Ok(DataStructure::MultiDimensionalStructure),
-            '3' => Ok(DataStructure::Unknown3),
+            '3' => Ok(DataStructure::ConcatenatedStructure),
             e => Err(ReadError::ParseError(format!(
                 "Invalid Data Structure Code: {}",
                 e
@@ -24,21 +30,25 @@ impl DataStructure {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum DataType {
     CharacterString = 0,
     ImplicitPoint = 1,
     ExplicitPoint = 2,
+    ExplicitPointScaled = 3,
+    CharacterBitString = 4,
     Binary = 5,
     Mixed = 6,
 }
 
 impl DataType {
-    fn from_char(value: char) -> ReadResult<DataType> {
+    pub(crate) fn from_char(value: char) -> ReadResult<DataType> {
         match value {
             '0' => Ok(DataType::CharacterString),
             '1' => Ok(DataType::ImplicitPoint),
             '2' => Ok(DataType::ExplicitPoint),
+            '3' => Ok(DataType::ExplicitPointScaled),
+            '4' => Ok(DataType::CharacterBitString),
             '5' => Ok(DataType::Binary),
             '6' => Ok(DataType::Mixed),
             e => Err(ReadError::ParseError(format!(
@@ -49,26 +59,81 @@ impl DataType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum LexicalLevel {
     Level0,
     Level1,
     Level2,
-    UnknownG,
+    /// UTF-8, as used by S-100
+    Utf8,
 }
 
 impl LexicalLevel {
-    fn from_str(value: String) -> ReadResult<LexicalLevel> {
-        match value.as_ref() {
-            "   " => Ok(LexicalLevel::Level0),
-            "-A " => Ok(LexicalLevel::Level1),
-            "%/@" => Ok(LexicalLevel::Level2),
-            //FIXME: Find out what this lexical level is
-            "%/G" => Ok(LexicalLevel::UnknownG),
-            e => Err(ReadError::ParseError(format!(
-                "Invalid Truncated Escape Sequence: {}",
-                e
-            ))),
+    /// The unit terminator, two bytes wide for the UCS-2 text of level 2
+    pub fn unit_terminator(&self) -> &'static [u8] {
+        match self {
+            LexicalLevel::Level2 => &[UNIT_TERMINATOR, 0x00],
+            _ => &[UNIT_TERMINATOR],
+        }
+    }
+
+    /// The field terminator, two bytes wide for the UCS-2 text of level 2
+    pub fn field_terminator(&self) -> &'static [u8] {
+        match self {
+            LexicalLevel::Level2 => &[FIELD_TERMINATOR, 0x00],
+            _ => &[FIELD_TERMINATOR],
+        }
+    }
+
+    /// The number of bytes taken by each character of a fixed width character subfield
+    pub fn character_width(&self) -> usize {
+        match self {
+            LexicalLevel::Level2 => 2,
+            _ => 1,
+        }
+    }
+
+    /// Decodes character data written at this lexical level, level 0 is ASCII, level 1
+    /// ISO 8859-1 and level 2 UCS-2 in little endian byte order
+    pub fn decode(&self, bytes: &[u8]) -> ReadResult<String> {
+        Ok(self.decode_borrowed(bytes)?.into_owned())
+    }
+
+    /// Decodes character data without copying it when the bytes are already valid UTF-8, as
+    /// for ASCII, UTF-8 and ISO 8859-1 text without accented characters
+    pub fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> ReadResult<Cow<'a, str>> {
+        match self {
+            LexicalLevel::Level0 => {
+                if !bytes.is_ascii() {
+                    return Err(ReadError::ParseError(format!(
+                        "Invalid ASCII character data: {}",
+                        String::from_utf8_lossy(bytes)
+                    )));
+                }
+                Ok(Cow::Borrowed(parse_str(bytes)?))
+            }
+            LexicalLevel::Level1 => {
+                if bytes.is_ascii() {
+                    return LexicalLevel::Level0.decode_borrowed(bytes);
+                }
+                Ok(Cow::Owned(bytes.iter().map(|b| *b as char).collect()))
+            }
+            LexicalLevel::Level2 => {
+                if bytes.len() % 2 != 0 {
+                    return Err(ReadError::ParseError(format!(
+                        "Invalid UCS-2 character data of {} bytes",
+                        bytes.len()
+                    )));
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                    .collect();
+                String::from_utf16(&units).map(Cow::Owned).map_err(|e| {
+                    ReadError::ParseError(format!("Invalid UCS-2 character data: {}", e))
+                })
+            }
+            LexicalLevel::Utf8 => Ok(Cow::Borrowed(parse_str(bytes)?)),
         }
     }
 }
@@ -77,76 +142,196 @@ impl LexicalLevel {
 pub struct FieldControls {
     data_structure: DataStructure,
     data_type: DataType,
+    character_set: CharacterSet,
     escape_sequence: LexicalLevel,
 }
 
-#[derive(Debug)]
-pub struct DataDescriptiveField {
-    field_controls: FieldControls,
-    field_name: String,
-    array_descriptor: String,
-    format_controls: String,
-}
-
-impl DataDescriptiveField {
-    pub fn read<T: Read + Seek>(
-        reader: &mut Reader<T>,
-        //FIXME: Find out how directory entry should be used
-        _entry: &DirectoryEntry,
-    ) -> ReadResult<DataDescriptiveField> {
-        // Data structure code
-        let data_structure = reader.read_char()?;
-        let data_structure = DataStructure::from_char(data_structure)?;
-
-        // Data type code
-        let data_type = reader.read_char()?;
-        let data_type = DataType::from_char(data_type)?;
+impl FieldControls {
+    /// Parses field controls read as a whole, e.g. `0000;&   `, where the printable graphics
+    /// may be left blank and a missing escape sequence means lexical level 0. Interchange level
+    /// 1 has no field controls at all.
+    pub(crate) fn parse(value: &str) -> ReadResult<FieldControls> {
+        let chars: Vec<char> = value.chars().collect();
+        if chars.is_empty() {
+            // without field controls a field is a single character string
+            return Ok(FieldControls {
+                data_structure: DataStructure::SingleDataItem,
+                data_type: DataType::CharacterString,
+                character_set: CharacterSet::parse("")?,
+                escape_sequence: LexicalLevel::Level0,
+            });
+        }
+        if chars.len() != 6 && chars.len() != 9 {
+            return Err(ReadError::ParseError(format!(
+                "Invalid Field Controls: {}",
+                value
+            )));
+        }
 
-        // Auxiliary controls must be "00"
-        let auxiliary_controls = reader.read_str(2)?;
+        let data_structure = DataStructure::from_char(chars[0])?;
+        let data_type = DataType::from_char(chars[1])?;
+        let auxiliary_controls: String = chars[2..4].iter().collect();
         if auxiliary_controls != "00" {
             return Err(ReadError::ParseError(format!(
                 "Invalid Auxiliary Controls: {}",
                 auxiliary_controls
             )));
         }
-        // Printable graphics must be ";&"
-        let printable_graphics = reader.read_str(2)?;
-        if printable_graphics != ";&" {
+        let printable_graphics: String = chars[4..6].iter().collect();
+        if printable_graphics != ";&" && printable_graphics != "  " {
             return Err(ReadError::ParseError(format!(
                 "Invalid Printable Graphics: {}",
                 printable_graphics
             )));
         }
-        // Truncated escape sequence
-        let escape_sequence = reader.read_str(3)?;
-        let escape_sequence = LexicalLevel::from_str(escape_sequence)?;
-        let field_name = reader.read_str_ut()?;
-        let array_descriptor = reader.read_str_ut()?;
-        let format_controls = reader.read_str_ft()?;
-
-        let field_controls = FieldControls {
+        let character_set = if chars.len() == 9 {
+            CharacterSet::parse(&chars[6..9].iter().collect::<String>())?
+        } else {
+            CharacterSet::parse("")?
+        };
+        let escape_sequence = character_set.lexical_level()?;
+
+        Ok(FieldControls {
             data_structure,
             data_type,
+            character_set,
             escape_sequence,
+        })
+    }
+
+    pub fn data_structure(&self) -> &DataStructure {
+        &self.data_structure
+    }
+
+    pub fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    /// The character set designated by the truncated escape sequence
+    pub fn character_set(&self) -> &CharacterSet {
+        &self.character_set
+    }
+
+    /// The lexical level of the character set designated by the truncated escape sequence
+    pub fn escape_sequence(&self) -> &LexicalLevel {
+        &self.escape_sequence
+    }
+}
+
+#[derive(Debug)]
+pub struct DataDescriptiveField {
+    tag: String,
+    field_controls: FieldControls,
+    field_name: String,
+    array_descriptor: ArrayDescriptor,
+    format_controls: FormatControls,
+    character_only: bool,
+}
+
+impl DataDescriptiveField {
+    pub fn read<T: Read>(
+        reader: &mut Reader<T>,
+        leader: &Leader,
+        entry: &DirectoryEntry,
+    ) -> ReadResult<DataDescriptiveField> {
+        let field_controls = reader.read_str(*leader.field_control_length() as usize)?;
+        let field_controls = FieldControls::parse(&field_controls)?;
+
+        // the field name, array descriptor and format controls are separated by unit
+        // terminators, the lower interchange levels may leave out the last two
+        // the descriptions are written in ASCII, or ISO 8859-1 for fields of lexical level 1,
+        // whatever the encoding of the data itself
+        let units = reader.read_bytes_ft()?;
+        let units = match field_controls.escape_sequence() {
+            LexicalLevel::Level0 | LexicalLevel::Level1 => {
+                field_controls.escape_sequence().decode(&units)?
+            }
+            _ => String::from_utf8(units)?,
+        };
+        let interchange_level = *leader.interchange_level();
+        let mut units = if interchange_level == '1' {
+            vec![units.as_str()]
+        } else {
+            units.split(UNIT_TERMINATOR as char).collect()
         };
+        units.resize(3, "");
+        let field_name = String::from(units[0]);
+        let format_controls = FormatControls::parse(units[2])?;
+        let array_descriptor = match field_controls.data_structure() {
+            DataStructure::ConcatenatedStructure => ArrayDescriptor::parse_concatenated(units[1])?,
+            _ => ArrayDescriptor::parse(units[1])?,
+        };
+        array_descriptor.validate(&format_controls)?;
+
+        // levels 1 and 2 only carry character data
+        let character_only = interchange_level != '3';
+        if character_only {
+            if let Some(format) =
+                format_controls
+                    .formats()
+                    .iter()
+                    .find(|format| match format.code() {
+                        FormatCode::BitString | FormatCode::Binary(_) => true,
+                        _ => false,
+                    })
+            {
+                return Err(ReadError::ParseError(format!(
+                    "Invalid Format Controls for interchange level {}: {}",
+                    interchange_level, format
+                )));
+            }
+        }
 
         Ok(DataDescriptiveField {
+            tag: entry.field_tag().clone(),
             field_controls,
             field_name,
             array_descriptor,
             format_controls,
+            character_only,
         })
     }
+
+    /// The tag of the fields described
+    pub fn tag(&self) -> &String {
+        &self.tag
+    }
+
+    pub fn field_controls(&self) -> &FieldControls {
+        &self.field_controls
+    }
+
+    pub fn field_name(&self) -> &String {
+        &self.field_name
+    }
+
+    pub fn array_descriptor(&self) -> &ArrayDescriptor {
+        &self.array_descriptor
+    }
+
+    pub fn format_controls(&self) -> &FormatControls {
+        &self.format_controls
+    }
+
+    /// Whether the data is read as character strings only, as for interchange levels 1 and 2
+    pub fn is_character_only(&self) -> bool {
+        self.character_only
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 pub(crate) mod tests {
     use crate::directory::tests::ascii_ddr_directory;
+    use crate::leader::tests::ascii_ddr_leader;
     use crate::{
-        ddr::DataDescriptiveField, ddr::Directory, ReadResult, Reader, FIELD_TERMINATOR,
-        UNIT_TERMINATOR,
+        ddr::{
+            ArrayDescriptor, DataDescriptiveField, DataStructure, DataType, Directory, LexicalLevel,
+        },
+        error::ReadError,
+        ReadResult, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR,
     };
+    use std::borrow::Cow;
     use std::io::{BufReader, Cursor};
 
     pub fn ascii_data_descriptive_field(
@@ -332,6 +517,15 @@ pub(crate) mod tests {
                 &[FIELD_TERMINATOR],
             ]
             .concat(),
+            [
+                "3600;&   Concatenated attribute field".as_bytes(),
+                &[UNIT_TERMINATOR],
+                "RCNM!RCID\\\\\\\\*ATTL!ATVL".as_bytes(),
+                &[UNIT_TERMINATOR],
+                "(b11,b14,b12,A)".as_bytes(),
+                &[FIELD_TERMINATOR],
+            ]
+            .concat(),
         ];
         let buffer = Cursor::new(&bytes[index]);
         let bufreader = BufReader::new(buffer);
@@ -339,17 +533,61 @@ pub(crate) mod tests {
 
         let entry = &directory.entries()[index];
 
-        let data_descriptive_field = DataDescriptiveField::read(&mut reader, &entry)?;
+        let leader = ascii_ddr_leader()?;
+        let data_descriptive_field = DataDescriptiveField::read(&mut reader, &leader, entry)?;
         Ok(data_descriptive_field)
     }
 
+    #[test]
+    fn test_data_types() {
+        let codes = ['0', '1', '2', '3', '4', '5', '6'];
+        let types = [
+            DataType::CharacterString,
+            DataType::ImplicitPoint,
+            DataType::ExplicitPoint,
+            DataType::ExplicitPointScaled,
+            DataType::CharacterBitString,
+            DataType::Binary,
+            DataType::Mixed,
+        ];
+        for (code, data_type) in codes.iter().zip(types.iter()) {
+            assert_eq!(&DataType::from_char(*code).unwrap(), data_type);
+        }
+        assert!(DataType::from_char('7').is_err());
+    }
+
     #[test]
     fn test_data_descriptive_fields() {
         let directory = ascii_ddr_directory().unwrap();
 
-        for i in 0..20 {
+        for i in 0..21 {
             let target = ascii_data_descriptive_field(i, &directory.1);
             assert_eq!(target.is_ok(), true);
         }
+
+        let target = ascii_data_descriptive_field(20, &directory.1).unwrap();
+        assert_eq!(
+            target.field_controls().data_structure(),
+            &DataStructure::ConcatenatedStructure
+        );
+        match target.array_descriptor() {
+            ArrayDescriptor::Concatenated { parts, .. } => assert_eq!(parts.len(), 2),
+            _ => panic!("expected a concatenated array descriptor"),
+        }
+    }
+
+    #[test]
+    fn test_lexical_level_decode() {
+        let target = LexicalLevel::Utf8
+            .decode_borrowed("Brésil".as_bytes())
+            .unwrap();
+        assert_eq!(target, Cow::Borrowed("Brésil"));
+        let target = LexicalLevel::Level1.decode_borrowed(b"Br\xe9sil").unwrap();
+        assert_eq!(target, Cow::<str>::Owned(String::from("Brésil")));
+
+        match LexicalLevel::Utf8.decode_borrowed(b"Br\xe9sil") {
+            Err(ReadError::Utf8Error(_)) => {}
+            e => panic!("{:?}", e),
+        }
     }
 }
@@ -1,9 +1,9 @@
 use crate::{
-    ddr::{DirectoryEntry, Leader},
+    ddr::{DataStructure, DataType, DirectoryEntry, FieldControls, Leader},
     error::ReadError,
-    ReadResult, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR,
+    ReadResult, Reader, FIELD_TERMINATOR,
 };
-use std::io::{Read, Seek};
+use std::io::Read;
 
 #[derive(Debug)]
 pub struct TagPair {
@@ -11,35 +11,69 @@ pub struct TagPair {
     child: String,
 }
 
+impl TagPair {
+    pub fn parent(&self) -> &String {
+        &self.parent
+    }
+
+    pub fn child(&self) -> &String {
+        &self.child
+    }
+}
+
 #[derive(Debug)]
 pub struct FileControlField {
+    field_controls: FieldControls,
+    external_file_title: String,
     tag_pairs: Vec<TagPair>,
 }
 
 impl FileControlField {
-    pub fn read<T: Read + Seek>(
+    pub fn read<T: Read>(
         reader: &mut Reader<T>,
         leader: &Leader,
         directory_entry: &DirectoryEntry,
     ) -> ReadResult<FileControlField> {
-        let field_controls = reader.read_str(*leader.field_control_length() as usize)?;
-        if field_controls != "0000;&   " {
+        let field_control_length = *leader.field_control_length() as usize;
+        let field_controls = reader.read_str(field_control_length)?;
+        let field_controls = FieldControls::parse(&field_controls)?;
+        // the tag pairs form a single vector of character data
+        let vector = match field_controls.data_structure() {
+            DataStructure::SingleDataItem | DataStructure::LinearStructure => true,
+            _ => false,
+        };
+        if !vector || *field_controls.data_type() != DataType::CharacterString {
             return Err(ReadError::ParseError(format!(
-                "Invalid Field Controls: {}",
-                field_controls
+                "Invalid File Control Field Controls: {:?} {:?}",
+                field_controls.data_structure(),
+                field_controls.data_type()
             )));
         }
 
-        // we should have a unit terminator here
-        if reader.read_u8()? != UNIT_TERMINATOR {
-            return Err(ReadError::ParseError(String::from(
-                "Did not find a unit terminator after the Field Controls",
-            )));
-        }
+        // the optional external file title ends with a unit terminator, both written at the
+        // lexical level of the field
+        let lexical_level = field_controls.escape_sequence();
+        let unit_terminator = lexical_level.unit_terminator();
+        let title = reader.read_bytes_until(unit_terminator)?;
+        let external_file_title = lexical_level.decode(&title)?;
 
-        // calculate the number of tag pairs
+        // the rest of the field up to the field terminator holds the tag pairs
         let tag_length = *leader.entry_map().field_tag() as usize;
-        let count = (*directory_entry.field_length() as usize - 11) / (2 * tag_length);
+        let remaining = (*directory_entry.field_length() as usize)
+            .checked_sub(field_control_length + title.len() + unit_terminator.len() + 1)
+            .ok_or_else(|| {
+                ReadError::ParseError(format!(
+                    "Invalid File Control Field length: {}",
+                    directory_entry.field_length()
+                ))
+            })?;
+        if remaining % (2 * tag_length) != 0 {
+            return Err(ReadError::ParseError(format!(
+                "Invalid File Control Field length: {} bytes of tag pairs",
+                remaining
+            )));
+        }
+        let count = remaining / (2 * tag_length);
         let mut tag_pairs: Vec<TagPair> = Vec::with_capacity(count);
         for _ in 0..count {
             let parent = reader.read_str(tag_length)?;
@@ -47,22 +81,40 @@ impl FileControlField {
             tag_pairs.push(TagPair { parent, child });
         }
 
-        // it should all end with a filed terminator here
+        // it should all end with a field terminator here
         if reader.read_u8()? != FIELD_TERMINATOR {
             return Err(ReadError::ParseError(String::from(
-                "Did not find a field terminator after the Field Controls",
+                "Did not find a field terminator after the Tag Pairs",
             )));
         }
 
-        Ok(FileControlField { tag_pairs })
+        Ok(FileControlField {
+            field_controls,
+            external_file_title,
+            tag_pairs,
+        })
+    }
+
+    pub fn field_controls(&self) -> &FieldControls {
+        &self.field_controls
+    }
+
+    /// The title of the file, empty when there is none
+    pub fn external_file_title(&self) -> &String {
+        &self.external_file_title
+    }
+
+    pub fn tag_pairs(&self) -> &Vec<TagPair> {
+        &self.tag_pairs
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 pub(crate) mod tests {
     use crate::directory::tests::ascii_ddr_directory;
     use crate::{
-        ddr::{Directory, FileControlField, Leader},
+        ddr::{DataStructure, Directory, DirectoryEntry, FileControlField, Leader, LexicalLevel},
         ReadResult, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR,
     };
     use std::io::{BufReader, Cursor};
@@ -98,4 +150,91 @@ pub(crate) mod tests {
         let target = target.unwrap().2;
         assert_eq!(target.tag_pairs.len(), 19);
     }
+
+    #[test]
+    fn test_file_control_field_with_title() {
+        let (leader, _, _) = ascii_file_control_field().unwrap();
+        let mut reader = Reader::new(Cursor::new("00000004900000".as_bytes()));
+        let entry = DirectoryEntry::read(&mut reader, &leader).unwrap();
+
+        let bytes = [
+            "1000  -A ".as_bytes(),
+            "S-57 test cell".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001FRIDFRIDFOIDFRIDATTF".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let target = FileControlField::read(&mut reader, &leader, &entry).unwrap();
+
+        assert_eq!(target.external_file_title(), "S-57 test cell");
+        assert_eq!(
+            target.field_controls().data_structure(),
+            &DataStructure::LinearStructure
+        );
+        assert_eq!(
+            target.field_controls().escape_sequence(),
+            &LexicalLevel::Level1
+        );
+        assert_eq!(target.tag_pairs().len(), 3);
+        assert_eq!(target.tag_pairs()[2].child(), "ATTF");
+
+        // a Latin-1 title is one byte per character
+        let mut reader = Reader::new(Cursor::new("00000005300000".as_bytes()));
+        let entry = DirectoryEntry::read(&mut reader, &leader).unwrap();
+        let bytes = [
+            "1000  -A ".as_bytes(),
+            b"Sao Paulo \xe9t\xe9 cell",
+            &[UNIT_TERMINATOR],
+            "0001FRIDFRIDFOIDFRIDATTF".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let target = FileControlField::read(&mut reader, &leader, &entry).unwrap();
+        assert_eq!(target.external_file_title(), "Sao Paulo été cell");
+        assert_eq!(target.tag_pairs().len(), 3);
+
+        // a UCS-2 title ends with a two byte unit terminator, 0x1f is also the second byte of "ἀ"
+        let mut reader = Reader::new(Cursor::new("00000004800000".as_bytes()));
+        let entry = DirectoryEntry::read(&mut reader, &leader).unwrap();
+        let title: Vec<u8> = "ἀ cell"
+            .encode_utf16()
+            .flat_map(|c| c.to_le_bytes().to_vec())
+            .collect();
+        let bytes = [
+            "1000  %/@".as_bytes(),
+            &title,
+            &[UNIT_TERMINATOR, 0x00],
+            "0001FRIDFRIDFOIDFRIDATTF".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let target = FileControlField::read(&mut reader, &leader, &entry).unwrap();
+        assert_eq!(target.external_file_title(), "ἀ cell");
+        assert_eq!(target.tag_pairs().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_file_control_field() {
+        let (leader, _, _) = ascii_file_control_field().unwrap();
+        let mut reader = Reader::new(Cursor::new("00000002000000".as_bytes()));
+        let entry = DirectoryEntry::read(&mut reader, &leader).unwrap();
+
+        let values = ["0500;&   ", "0000;&   ", "0010;&   "];
+        let tags = ["0001FRID", "0001FRI", "0001FRID"];
+        for (field_controls, tags) in values.iter().zip(tags.iter()) {
+            let bytes = [
+                field_controls.as_bytes(),
+                &[UNIT_TERMINATOR],
+                tags.as_bytes(),
+                &[FIELD_TERMINATOR],
+            ]
+            .concat();
+            let mut reader = Reader::new(Cursor::new(bytes));
+            assert!(FileControlField::read(&mut reader, &leader, &entry).is_err());
+        }
+    }
 }
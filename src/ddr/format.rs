@@ -0,0 +1,421 @@
+use crate::{error::ReadError, ReadResult};
+use std::fmt;
+
+/// The form of a binary (`b`) format control
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum BinaryForm {
+    UnsignedInteger = 1,
+    SignedInteger = 2,
+    UnsignedFixedPoint = 3,
+    SignedFixedPoint = 4,
+    FloatingPoint = 5,
+}
+
+impl BinaryForm {
+    fn from_char(value: char) -> Option<BinaryForm> {
+        match value {
+            '1' => Some(BinaryForm::UnsignedInteger),
+            '2' => Some(BinaryForm::SignedInteger),
+            '3' => Some(BinaryForm::UnsignedFixedPoint),
+            '4' => Some(BinaryForm::SignedFixedPoint),
+            '5' => Some(BinaryForm::FloatingPoint),
+            _ => None,
+        }
+    }
+}
+
+/// The data type of a single format control
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum FormatCode {
+    /// `A`: character data
+    CharacterData,
+    /// `I`: implicit point representation
+    ImplicitPoint,
+    /// `R`: explicit point representation
+    ExplicitPoint,
+    /// `S`: explicit point scaled representation
+    ExplicitPointScaled,
+    /// `C`: character mode bit string
+    CharacterBitString,
+    /// `B`: bit string
+    BitString,
+    /// `X`: skipped characters
+    Skip,
+    /// `b`: binary form
+    Binary(BinaryForm),
+}
+
+/// The width of a single format control
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum FormatWidth {
+    /// Fixed number of characters, bytes for binary forms or bits for bit strings
+    Fixed(usize),
+    /// Variable width, delimited by a unit terminator
+    Delimited,
+}
+
+/// An elementary format control, e.g. `b11`, `A(3)`, `B(40)` or `R`
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Format {
+    code: FormatCode,
+    width: FormatWidth,
+}
+
+impl Format {
+    pub fn code(&self) -> &FormatCode {
+        &self.code
+    }
+
+    pub fn width(&self) -> &FormatWidth {
+        &self.width
+    }
+
+    /// Number of bytes taken by the subfield, `None` when it is delimited
+    pub fn byte_width(&self) -> Option<usize> {
+        match (self.code, self.width) {
+            (_, FormatWidth::Delimited) => None,
+            (FormatCode::BitString, FormatWidth::Fixed(bits)) => Some((bits + 7) / 8),
+            (_, FormatWidth::Fixed(width)) => Some(width),
+        }
+    }
+}
+
+/// A format control item with its repetition factor
+#[derive(Debug, PartialEq, Clone)]
+pub enum FormatItem {
+    /// An elementary format, e.g. `2b12`
+    Format { repeat: usize, format: Format },
+    /// A parenthesised group of items, e.g. `3(A,I)`
+    Group {
+        repeat: usize,
+        items: Vec<FormatItem>,
+    },
+}
+
+impl FormatItem {
+    fn expand(&self, formats: &mut Vec<Format>) {
+        match self {
+            FormatItem::Format { repeat, format } => {
+                for _ in 0..*repeat {
+                    formats.push(*format);
+                }
+            }
+            FormatItem::Group { repeat, items } => {
+                for _ in 0..*repeat {
+                    for item in items {
+                        item.expand(formats);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The parsed format controls of a data descriptive field
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct FormatControls {
+    items: Vec<FormatItem>,
+}
+
+impl FormatControls {
+    pub fn parse(value: &str) -> ReadResult<FormatControls> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(FormatControls::default());
+        }
+
+        let mut parser = FormatParser {
+            source: value,
+            chars: value.chars().collect(),
+            position: 0,
+        };
+        parser.expect('(')?;
+        let items = parser.parse_items()?;
+        parser.expect(')')?;
+        if parser.position != parser.chars.len() {
+            return Err(parser.error("unexpected characters after the closing parenthesis"));
+        }
+
+        Ok(FormatControls { items })
+    }
+
+    pub fn items(&self) -> &Vec<FormatItem> {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The elementary formats with all repetition factors and groups expanded, one per subfield
+    pub fn formats(&self) -> Vec<Format> {
+        let mut formats = Vec::new();
+        for item in &self.items {
+            item.expand(&mut formats);
+        }
+        formats
+    }
+}
+
+struct FormatParser<'a> {
+    source: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl<'a> FormatParser<'a> {
+    fn error(&self, reason: &str) -> ReadError {
+        ReadError::ParseError(format!(
+            "Invalid Format Controls: {} ({} at position {})",
+            self.source, reason, self.position
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        if c.is_some() {
+            self.position += 1;
+        }
+        c
+    }
+
+    fn expect(&mut self, expected: char) -> ReadResult<()> {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", expected)))
+        }
+    }
+
+    fn parse_number(&mut self) -> Option<usize> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            self.position += 1;
+        }
+        if start == self.position {
+            None
+        } else {
+            let digits: String = self.chars[start..self.position].iter().collect();
+            digits.parse::<usize>().ok()
+        }
+    }
+
+    fn parse_items(&mut self) -> ReadResult<Vec<FormatItem>> {
+        let mut items = vec![self.parse_item()?];
+        while self.peek() == Some(',') {
+            self.position += 1;
+            items.push(self.parse_item()?);
+        }
+        Ok(items)
+    }
+
+    fn parse_item(&mut self) -> ReadResult<FormatItem> {
+        let repeat = self.parse_number().unwrap_or(1);
+        if repeat == 0 {
+            return Err(self.error("repetition factor must be greater than zero"));
+        }
+
+        if self.peek() == Some('(') {
+            self.position += 1;
+            let items = self.parse_items()?;
+            self.expect(')')?;
+            return Ok(FormatItem::Group { repeat, items });
+        }
+
+        let format = self.parse_format()?;
+        Ok(FormatItem::Format { repeat, format })
+    }
+
+    fn parse_format(&mut self) -> ReadResult<Format> {
+        let code = match self.next() {
+            Some('A') => FormatCode::CharacterData,
+            Some('I') => FormatCode::ImplicitPoint,
+            Some('R') => FormatCode::ExplicitPoint,
+            Some('S') => FormatCode::ExplicitPointScaled,
+            Some('C') => FormatCode::CharacterBitString,
+            Some('B') => FormatCode::BitString,
+            Some('X') => FormatCode::Skip,
+            Some('b') => {
+                let form = self.next().and_then(BinaryForm::from_char);
+                match form {
+                    Some(form) => {
+                        let width = match self.parse_number() {
+                            Some(width) if width > 0 => width,
+                            _ => return Err(self.error("expected a binary width")),
+                        };
+                        return Ok(Format {
+                            code: FormatCode::Binary(form),
+                            width: FormatWidth::Fixed(width),
+                        });
+                    }
+                    None => return Err(self.error("expected a binary form between 1 and 5")),
+                }
+            }
+            Some(c) => return Err(self.error(&format!("unknown format code '{}'", c))),
+            None => return Err(self.error("expected a format code")),
+        };
+
+        let width = if self.peek() == Some('(') {
+            self.position += 1;
+            let width = match self.parse_number() {
+                Some(width) if width > 0 => width,
+                _ => return Err(self.error("expected a width")),
+            };
+            self.expect(')')?;
+            FormatWidth::Fixed(width)
+        } else {
+            FormatWidth::Delimited
+        };
+
+        if code == FormatCode::BitString && width == FormatWidth::Delimited {
+            return Err(self.error("bit strings require a width"));
+        }
+
+        Ok(Format { code, width })
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self.code {
+            FormatCode::CharacterData => 'A',
+            FormatCode::ImplicitPoint => 'I',
+            FormatCode::ExplicitPoint => 'R',
+            FormatCode::ExplicitPointScaled => 'S',
+            FormatCode::CharacterBitString => 'C',
+            FormatCode::BitString => 'B',
+            FormatCode::Skip => 'X',
+            FormatCode::Binary(form) => {
+                if let FormatWidth::Fixed(width) = self.width {
+                    return write!(f, "b{}{}", form as u8, width);
+                }
+                'b'
+            }
+        };
+        match self.width {
+            FormatWidth::Fixed(width) => write!(f, "{}({})", code, width),
+            FormatWidth::Delimited => write!(f, "{}", code),
+        }
+    }
+}
+
+impl fmt::Display for FormatItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let repeat = match self {
+            FormatItem::Format { repeat, .. } | FormatItem::Group { repeat, .. } => *repeat,
+        };
+        if repeat != 1 {
+            write!(f, "{}", repeat)?;
+        }
+        match self {
+            FormatItem::Format { format, .. } => write!(f, "{}", format),
+            FormatItem::Group { items, .. } => write_items(f, items),
+        }
+    }
+}
+
+impl fmt::Display for FormatControls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.items.is_empty() {
+            return Ok(());
+        }
+        write_items(f, &self.items)
+    }
+}
+
+fn write_items(f: &mut fmt::Formatter<'_>, items: &[FormatItem]) -> fmt::Result {
+    write!(f, "(")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    write!(f, ")")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::ddr::{BinaryForm, FormatCode, FormatControls, FormatItem, FormatWidth};
+
+    #[test]
+    fn test_format_controls() {
+        let values = [
+            "(b12)",
+            "(b11,b14,2b11,2b12,b11)",
+            "(b12,A)",
+            "(B(64),b11,A)",
+            "(B(40),3b11)",
+            "(2b24)",
+            "(A(2),I(10),3A,A(3),4R,2A)",
+            "(A,2(I(2),R))",
+        ];
+        let counts = [1, 7, 2, 3, 4, 2, 12, 5];
+
+        for (value, count) in values.iter().zip(counts.iter()) {
+            let target = FormatControls::parse(value).unwrap();
+            assert_eq!(target.formats().len(), *count);
+            assert_eq!(&target.to_string(), value);
+        }
+    }
+
+    #[test]
+    fn test_format_items() {
+        let target = FormatControls::parse("(B(40),3b11,2(A,I(5)))").unwrap();
+        let items = target.items();
+        assert_eq!(items.len(), 3);
+
+        match &items[0] {
+            FormatItem::Format { repeat, format } => {
+                assert_eq!(*repeat, 1);
+                assert_eq!(format.code(), &FormatCode::BitString);
+                assert_eq!(format.width(), &FormatWidth::Fixed(40));
+                assert_eq!(format.byte_width(), Some(5));
+            }
+            _ => panic!("expected a format"),
+        }
+        match &items[1] {
+            FormatItem::Format { repeat, format } => {
+                assert_eq!(*repeat, 3);
+                assert_eq!(
+                    format.code(),
+                    &FormatCode::Binary(BinaryForm::UnsignedInteger)
+                );
+                assert_eq!(format.byte_width(), Some(1));
+            }
+            _ => panic!("expected a format"),
+        }
+        match &items[2] {
+            FormatItem::Group { repeat, items } => {
+                assert_eq!(*repeat, 2);
+                assert_eq!(items.len(), 2);
+            }
+            _ => panic!("expected a group"),
+        }
+    }
+
+    #[test]
+    fn test_empty_format_controls() {
+        let target = FormatControls::parse("").unwrap();
+        assert!(target.is_empty());
+        assert_eq!(target.formats().len(), 0);
+    }
+
+    #[test]
+    fn test_invalid_format_controls() {
+        let values = [
+            "b12", "(b12", "(b62)", "(b1)", "(Q)", "(A(x))", "(0A)", "(B)", "(A,)", "(A))",
+        ];
+        for value in &values {
+            assert!(FormatControls::parse(value).is_err(), "{}", value);
+        }
+    }
+}
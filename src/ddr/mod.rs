@@ -1,13 +1,33 @@
 pub use crate::directory::Directory;
 pub use crate::directory_entry::DirectoryEntry;
 
-pub use crate::leader::Leader;
+pub use crate::leader::{EntryMap, Leader};
 
 mod ddf;
+#[cfg(test)]
+pub(crate) use ddf::tests::ascii_data_descriptive_field;
 pub use ddf::{DataDescriptiveField, DataStructure, DataType, FieldControls, LexicalLevel};
 
+mod charset;
+pub use charset::CharacterSet;
+
+mod array;
+pub use array::ArrayDescriptor;
+
+mod format;
+pub use format::{BinaryForm, Format, FormatCode, FormatControls, FormatItem, FormatWidth};
+
 mod fcf;
 pub use fcf::{FileControlField, TagPair};
 
+mod control;
+pub use control::{
+    UserAugmentedFileDescription, FILE_CONTROL_TAG, RECORD_IDENTIFIER_TAG, RECURSIVE_TREE_LINK_TAG,
+    USER_AUGMENTED_FILE_DESCRIPTION_TAG,
+};
+
+mod tree;
+pub use tree::FieldTree;
+
 mod record;
 pub use record::DataDescriptiveRecord;
@@ -1,31 +1,58 @@
 use crate::{
-    ddr::{DataDescriptiveField, Directory, FileControlField, Leader},
-    ReadResult, Reader,
+    ddr::{
+        DataDescriptiveField, Directory, DirectoryEntry, FieldTree, FileControlField, Leader,
+        UserAugmentedFileDescription, USER_AUGMENTED_FILE_DESCRIPTION_TAG,
+    },
+    error::ReadError,
+    ReadResult, Reader, ReaderOptions,
 };
-use std::io::{Read, Seek};
+use std::io::{Cursor, Read};
 
 #[derive(Debug)]
 pub struct DataDescriptiveRecord {
     leader: Leader,
     directory: Directory,
     file_control_field: FileControlField,
+    field_tree: FieldTree,
+    user_augmented_file_description: Option<UserAugmentedFileDescription>,
     data_descriptive_fields: Vec<DataDescriptiveField>,
 }
 
 impl DataDescriptiveRecord {
-    pub fn read<T: Read + Seek>(reader: &mut Reader<T>) -> ReadResult<DataDescriptiveRecord> {
-        let leader = Leader::read_ddr(reader)?;
+    pub fn read<T: Read>(
+        reader: &mut Reader<T>,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveRecord> {
+        let leader = Leader::read_ddr(reader, options)?;
 
         let directory = Directory::read(reader, &leader)?;
 
+        let field_area = directory.read_field_area(reader, &leader)?;
+
         let entries = directory.entries();
+        if entries.is_empty() {
+            return Err(ReadError::ParseError(String::from(
+                "Did not find a File Control Field in the Data Descriptive Record",
+            )));
+        }
 
-        let file_control_field = FileControlField::read(reader, &leader, &entries[0])?;
+        let mut field_reader = DataDescriptiveRecord::field_reader(&entries[0], &field_area)?;
+        let file_control_field = FileControlField::read(&mut field_reader, &leader, &entries[0])?;
+        let field_tree = FieldTree::new(&file_control_field)?;
 
+        let mut user_augmented_file_description = None;
         let mut data_descriptive_fields: Vec<DataDescriptiveField> =
             Vec::with_capacity(entries.len() - 1);
-        for i in 1..entries.len() {
-            let ddf = DataDescriptiveField::read(reader, &entries[i])?;
+        for entry in entries.iter().skip(1) {
+            let mut field_reader = DataDescriptiveRecord::field_reader(entry, &field_area)?;
+            if entry.field_tag() == USER_AUGMENTED_FILE_DESCRIPTION_TAG {
+                user_augmented_file_description = Some(UserAugmentedFileDescription::read(
+                    &mut field_reader,
+                    &leader,
+                )?);
+                continue;
+            }
+            let ddf = DataDescriptiveField::read(&mut field_reader, &leader, entry)?;
             data_descriptive_fields.push(ddf);
         }
 
@@ -33,11 +60,227 @@ impl DataDescriptiveRecord {
             leader,
             directory,
             file_control_field,
+            field_tree,
+            user_augmented_file_description,
             data_descriptive_fields,
         })
     }
 
+    /// A reader limited to the bytes of a single field of the field area
+    fn field_reader<'a>(
+        entry: &DirectoryEntry,
+        field_area: &'a [u8],
+    ) -> ReadResult<Reader<Cursor<&'a [u8]>>> {
+        let data = entry.field_data(field_area)?;
+        Ok(Reader::new(Cursor::new(data)))
+    }
+
     pub fn leader(&self) -> &Leader {
         &self.leader
     }
+
+    pub fn directory(&self) -> &Directory {
+        &self.directory
+    }
+
+    pub fn file_control_field(&self) -> &FileControlField {
+        &self.file_control_field
+    }
+
+    /// The field hierarchy defined by the file control field
+    pub fn field_tree(&self) -> &FieldTree {
+        &self.field_tree
+    }
+
+    /// The `0002` field describing the file, if there is one
+    pub fn user_augmented_file_description(&self) -> Option<&UserAugmentedFileDescription> {
+        self.user_augmented_file_description.as_ref()
+    }
+
+    pub fn data_descriptive_fields(&self) -> &Vec<DataDescriptiveField> {
+        &self.data_descriptive_fields
+    }
+
+    /// The tags of the fields defined in this record, in directory order
+    pub fn tags(&self) -> Vec<&String> {
+        self.data_descriptive_fields
+            .iter()
+            .map(|ddf| ddf.tag())
+            .collect()
+    }
+
+    /// Finds the Data Descriptive Field that describes the fields with the given tag
+    pub fn data_descriptive_field(&self, tag: &str) -> Option<&DataDescriptiveField> {
+        self.data_descriptive_fields
+            .iter()
+            .find(|ddf| ddf.tag() == tag)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        ddr::LexicalLevel,
+        dr::{RecordIdentifier, SubfieldValue},
+        DataDescriptiveFile, ReaderOptions, FIELD_TERMINATOR, UNIT_TERMINATOR,
+    };
+
+    #[test]
+    fn test_interchange_levels() {
+        let bytes = [
+            // level 1 has no field controls, only the field names
+            "001091LE1 0000058 ! 3404".as_bytes(),
+            "00000100000NAME0170010DESC0240027".as_bytes(),
+            &[FIELD_TERMINATOR],
+            &[UNIT_TERMINATOR],
+            "NAMEDESC".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "Name of the item".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "Description of the item".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00066 D     00047   3404".as_bytes(),
+            "NAME0080000DESC0110008".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "Buoy 12".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "Lit, 2.5 m".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target = DataDescriptiveFile::from_bytes(&bytes).unwrap();
+
+        let ddr = target.data_descriptive_record();
+        assert_eq!(*ddr.leader().interchange_level(), '1');
+        assert_eq!(ddr.field_tree().parent("DESC"), Some("NAME"));
+        let desc = ddr.data_descriptive_field("DESC").unwrap();
+        assert_eq!(desc.field_name(), "Description of the item");
+        assert!(desc.is_character_only());
+        assert_eq!(
+            target.data_records()[0]
+                .data_field("DESC")
+                .unwrap()
+                .values(),
+            &vec![SubfieldValue::String(String::from("Lit, 2.5 m"))]
+        );
+
+        let bytes = [
+            "001412LE1 0900058 ! 3404".as_bytes(),
+            "00000190000ITEM0360019POSN0280055".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "ITEMPOSN".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1000;&   Item".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "NAME!CODE".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A(4),I(2))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1000;&   Position".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "*LAT!LON".as_bytes(),
+            &[UNIT_TERMINATOR, FIELD_TERMINATOR],
+            "00065 D     00047   3404".as_bytes(),
+            "ITEM0070000POSN0110007".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "Buoy07".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "50.1".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "-1.2".as_bytes(),
+            &[UNIT_TERMINATOR, FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target = DataDescriptiveFile::from_bytes(&bytes).unwrap();
+
+        let data_record = &target.data_records()[0];
+        let item = data_record.data_field("ITEM").unwrap();
+        assert_eq!(item.get_str("NAME"), Some("Buoy"));
+        assert_eq!(
+            item.get("CODE"),
+            Some(&SubfieldValue::String(String::from("07")))
+        );
+        let posn = data_record.data_field("POSN").unwrap();
+        assert_eq!(posn.get_str("LAT"), Some("50.1"));
+        assert_eq!(posn.get_str("LON"), Some("-1.2"));
+
+        // binary formats are not allowed at level 2
+        let bytes = [
+            "001012LE1 0900047 ! 3404".as_bytes(),
+            "00000190000ITEM0350019".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "ITEMPOSN".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1000;&   Item".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "NAME!CODE".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A(4),b12)".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        assert!(DataDescriptiveFile::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_permissive_records() {
+        // a 1985 edition leader with six character field controls and no extended character set
+        let bytes = [
+            "001472L   0600058   3404".as_bytes(),
+            "0000016000000010300016IDEN0430046".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001IDEN".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&DDF RECORD IDENTIFIER".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR, FIELD_TERMINATOR],
+            "1600;&IDENTIFICATION".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "MODN!RCID".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A(4),I(6))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00060 D     00047   3404".as_bytes(),
+            "00010020000IDEN0110002".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "IDEN     1".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        assert!(DataDescriptiveFile::from_bytes(&bytes).is_err());
+        let target =
+            DataDescriptiveFile::from_reader_with_options(&bytes[..], &ReaderOptions::permissive())
+                .unwrap();
+
+        let leader = target.data_descriptive_record().leader();
+        assert_eq!(*leader.field_control_length(), 6);
+        assert_eq!(*leader.code_extension(), ' ');
+        assert_eq!(leader.character_set(), "   ");
+        let iden = target
+            .data_descriptive_record()
+            .data_descriptive_field("IDEN")
+            .unwrap();
+        assert_eq!(
+            *iden.field_controls().escape_sequence(),
+            LexicalLevel::Level0
+        );
+        assert_eq!(iden.field_name(), "IDENTIFICATION");
+
+        let data_record = &target.data_records()[0];
+        assert_eq!(
+            data_record.record_identifier(),
+            Some(&RecordIdentifier::Number(1))
+        );
+        assert_eq!(
+            data_record.data_field("IDEN").unwrap().get_str("MODN"),
+            Some("IDEN")
+        );
+    }
 }
@@ -0,0 +1,182 @@
+use crate::{ddr::FileControlField, error::ReadError, ReadResult};
+
+/// The field hierarchy defined by the tag pairs of the file control field
+#[derive(Debug, Default)]
+pub struct FieldTree {
+    /// `(parent, child)` tags, each child has a single parent
+    links: Vec<(String, String)>,
+}
+
+impl FieldTree {
+    pub fn new(file_control_field: &FileControlField) -> ReadResult<FieldTree> {
+        let mut links: Vec<(String, String)> = Vec::new();
+        for tag_pair in file_control_field.tag_pairs() {
+            let (parent, child) = (tag_pair.parent(), tag_pair.child());
+            match links.iter().find(|(_, c)| c == child) {
+                Some((p, _)) if p == parent => continue,
+                Some((p, _)) => {
+                    return Err(ReadError::ParseError(format!(
+                        "Field {} has two parents in the Field Tree: {} and {}",
+                        child, p, parent
+                    )))
+                }
+                None => links.push((parent.clone(), child.clone())),
+            }
+        }
+
+        let tree = FieldTree { links };
+        for (_, child) in &tree.links {
+            if tree.ancestors(child).any(|ancestor| ancestor == child) {
+                return Err(ReadError::ParseError(format!(
+                    "Field {} is its own ancestor in the Field Tree",
+                    child
+                )));
+            }
+        }
+        Ok(tree)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    /// Whether the tag appears in the tree
+    pub fn contains(&self, tag: &str) -> bool {
+        self.links.iter().any(|(p, c)| p == tag || c == tag)
+    }
+
+    pub fn parent(&self, tag: &str) -> Option<&str> {
+        self.links
+            .iter()
+            .find(|(_, c)| c == tag)
+            .map(|(p, _)| p.as_str())
+    }
+
+    pub fn children(&self, tag: &str) -> Vec<&str> {
+        self.links
+            .iter()
+            .filter(|(p, _)| p == tag)
+            .map(|(_, c)| c.as_str())
+            .collect()
+    }
+
+    /// The tags that have no parent
+    pub fn roots(&self) -> Vec<&str> {
+        let mut roots: Vec<&str> = Vec::new();
+        for (parent, _) in &self.links {
+            if self.parent(parent).is_none() && !roots.contains(&parent.as_str()) {
+                roots.push(parent);
+            }
+        }
+        roots
+    }
+
+    /// The parent, grandparent and further ancestors of the tag
+    pub fn ancestors<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        let mut current = tag;
+        let mut steps = 0;
+        std::iter::from_fn(move || {
+            // a cycle can not be longer than the number of links
+            if steps > self.links.len() {
+                return None;
+            }
+            steps += 1;
+            current = self.parent(current)?;
+            Some(current)
+        })
+    }
+
+    /// Works out the parent of each field of a record from the order of their tags, checking
+    /// that each field follows its parent or one of its parent's descendants
+    pub fn link(&self, tags: &[&str]) -> ReadResult<Vec<Option<usize>>> {
+        let mut parents: Vec<Option<usize>> = Vec::with_capacity(tags.len());
+        if self.is_empty() {
+            parents.resize(tags.len(), None);
+            return Ok(parents);
+        }
+
+        let mut stack: Vec<usize> = Vec::new();
+        for (index, tag) in tags.iter().enumerate() {
+            // a field outside the tree has no parent and leaves the fields around it linked
+            if !self.contains(tag) {
+                parents.push(None);
+                continue;
+            }
+            let parent = match self.parent(tag) {
+                Some(parent) => {
+                    while let Some(top) = stack.last() {
+                        if tags[*top] == parent {
+                            break;
+                        }
+                        stack.pop();
+                    }
+                    match stack.last() {
+                        Some(top) => Some(*top),
+                        None => {
+                            return Err(ReadError::ParseError(format!(
+                                "Field {} at position {} does not follow its parent field {}",
+                                tag, index, parent
+                            )))
+                        }
+                    }
+                }
+                None => {
+                    stack.clear();
+                    None
+                }
+            };
+            parents.push(parent);
+            stack.push(index);
+        }
+        Ok(parents)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::ddr::fcf::tests::ascii_file_control_field;
+    use crate::ddr::FieldTree;
+
+    #[test]
+    fn test_field_tree() {
+        let file_control_field = ascii_file_control_field().unwrap().2;
+        let target = FieldTree::new(&file_control_field).unwrap();
+
+        assert_eq!(target.roots(), vec!["0001"]);
+        assert_eq!(target.children("0001"), vec!["FRID", "VRID"]);
+        assert_eq!(target.parent("ATTF"), Some("FRID"));
+        assert_eq!(target.parent("0001"), None);
+        assert_eq!(
+            target.ancestors("AR2D").collect::<Vec<&str>>(),
+            vec!["ARCC", "VRID", "0001"]
+        );
+        assert!(target.contains("SG2D"));
+        assert!(!target.contains("DSID"));
+    }
+
+    #[test]
+    fn test_link() {
+        let file_control_field = ascii_file_control_field().unwrap().2;
+        let target = FieldTree::new(&file_control_field).unwrap();
+
+        let parents = target
+            .link(&["0001", "FRID", "FOID", "ATTF", "ATTF", "FSPT"])
+            .unwrap();
+        assert_eq!(
+            parents,
+            vec![None, Some(0), Some(1), Some(1), Some(1), Some(1)]
+        );
+
+        let parents = target
+            .link(&["0001", "VRID", "ARCC", "AR2D", "ATTV"])
+            .unwrap();
+        assert_eq!(parents, vec![None, Some(0), Some(1), Some(2), Some(1)]);
+
+        // a tag unknown to the tree between a parent and its child
+        let parents = target.link(&["0001", "FRID", "DSID", "ATTF"]).unwrap();
+        assert_eq!(parents, vec![None, Some(0), None, Some(1)]);
+
+        assert!(target.link(&["0001", "ATTF"]).is_err());
+        assert!(target.link(&["FRID", "0001"]).is_err());
+    }
+}
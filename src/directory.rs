@@ -1,18 +1,21 @@
 use crate::{
-    directory_entry::DirectoryEntry, leader::Leader, ReadResult, Reader, FIELD_TERMINATOR,
+    directory_entry::DirectoryEntry,
+    error::ReadError,
+    leader::{Leader, LEADER_LENGTH},
+    ReadResult, Reader, FIELD_TERMINATOR,
 };
-use std::io::{Read, Seek};
+use std::io::Read;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Directory {
     entries: Vec<DirectoryEntry>,
 }
 
 impl Directory {
-    pub fn read<T: Read + Seek>(reader: &mut Reader<T>, leader: &Leader) -> ReadResult<Directory> {
+    pub fn read<T: Read>(reader: &mut Reader<T>, leader: &Leader) -> ReadResult<Directory> {
         let mut entries: Vec<DirectoryEntry> = Vec::new();
 
-        while reader.peek_byte().unwrap() != FIELD_TERMINATOR {
+        while reader.peek_byte()? != FIELD_TERMINATOR {
             let entry = DirectoryEntry::read(reader, leader)?;
             entries.push(entry);
         }
@@ -20,7 +23,120 @@ impl Directory {
         // Go past the field terminator
         reader.read_char()?;
 
-        Ok(Directory { entries })
+        let directory = Directory { entries };
+        if *leader.record_length() == 0 {
+            directory.check_overflow(leader)?;
+        }
+        Ok(directory)
+    }
+
+    /// Only records too large for the leader are checked. Their fields usually follow each
+    /// other in the field area, so a position or length that does not fit the width given by
+    /// the entry map shows up as a field that starts too early or too late
+    fn check_overflow(&self, leader: &Leader) -> ReadResult<()> {
+        let entry_map = leader.entry_map();
+        let position_limit = 10u64.checked_pow(*entry_map.field_position() as u32);
+        let length_limit = 10u64.checked_pow(*entry_map.field_length() as u32);
+
+        for pair in self.entries.windows(2) {
+            let (previous, entry) = (&pair[0], &pair[1]);
+            let expected = previous.field_position() + previous.field_length();
+            let position = *entry.field_position();
+            if position == expected {
+                continue;
+            }
+            if let Some(limit) = position_limit {
+                if expected >= limit && position == expected % limit {
+                    return Err(ReadError::ParseError(format!(
+                        "Position {} of field {} overflows the {} digits of the entry map",
+                        expected,
+                        entry.field_tag(),
+                        entry_map.field_position()
+                    )));
+                }
+            }
+            if let Some(limit) = length_limit {
+                if position > expected && (position - expected) % limit == 0 {
+                    return Err(ReadError::ParseError(format!(
+                        "Length {} of field {} overflows the {} digits of the entry map",
+                        position - previous.field_position(),
+                        previous.field_tag(),
+                        entry_map.field_length()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of bytes in the field area. Records too large for the five digits of the
+    /// leader have a record length of zero and take the extent of the field area from the
+    /// directory.
+    pub fn field_area_length(&self, leader: &Leader) -> ReadResult<usize> {
+        let record_length = *leader.record_length() as usize;
+        let base_address = *leader.base_address() as usize;
+        if record_length == 0 {
+            return Ok(self
+                .entries
+                .iter()
+                .map(|entry| (entry.field_position() + entry.field_length()) as usize)
+                .max()
+                .unwrap_or(0));
+        }
+        if record_length < base_address {
+            return Err(ReadError::ParseError(format!(
+                "Record Length {} is smaller than the Base Address Of Field Area {}",
+                record_length, base_address
+            )));
+        }
+        Ok(record_length - base_address)
+    }
+
+    /// The offset of the field area from the start of the record, which is the base address
+    /// as long as it leaves room for the directory
+    pub(crate) fn field_area_offset(&self, leader: &Leader) -> ReadResult<usize> {
+        let base_address = *leader.base_address() as usize;
+        let directory_end = self.directory_end(leader);
+        if base_address < directory_end {
+            return Err(ReadError::ParseError(format!(
+                "Base Address Of Field Area {} is inside the directory ending at {}",
+                base_address, directory_end
+            )));
+        }
+        Ok(base_address)
+    }
+
+    /// The offset just after the field terminator of the directory
+    fn directory_end(&self, leader: &Leader) -> usize {
+        let entry_map = leader.entry_map();
+        let entry_length = (*entry_map.field_tag()
+            + *entry_map.field_length()
+            + *entry_map.field_position()) as usize;
+        LEADER_LENGTH + self.entries.len() * entry_length + 1
+    }
+
+    /// Reads the field area that follows the directory, skipping any padding before the base
+    /// address of the field area
+    pub fn read_field_area<T: Read>(
+        &self,
+        reader: &mut Reader<T>,
+        leader: &Leader,
+    ) -> ReadResult<Vec<u8>> {
+        self.skip_to_field_area(reader, leader)?;
+
+        let length = self.field_area_length(leader)?;
+        reader.read_bytes(length)
+    }
+
+    /// Skips any padding between the directory and the base address of the field area
+    pub(crate) fn skip_to_field_area<T: Read>(
+        &self,
+        reader: &mut Reader<T>,
+        leader: &Leader,
+    ) -> ReadResult<()> {
+        let offset = self.field_area_offset(leader)?;
+        reader.read_bytes(offset - self.directory_end(leader))?;
+        Ok(())
     }
 
     pub fn entries(&self) -> &Vec<DirectoryEntry> {
@@ -29,9 +145,13 @@ impl Directory {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 pub(crate) mod tests {
     use crate::leader::tests::ascii_ddr_leader;
-    use crate::{directory::Directory, leader::Leader, ReadResult, Reader, FIELD_TERMINATOR};
+    use crate::{
+        directory::Directory, dr::RecordIdentifier, error::ReadError, leader::Leader,
+        DataDescriptiveFile, ReadResult, Reader, ReaderOptions, FIELD_TERMINATOR, UNIT_TERMINATOR,
+    };
     use std::io::{BufReader, Cursor};
 
     pub fn ascii_ddr_directory() -> ReadResult<(Leader, Directory)> {
@@ -64,4 +184,151 @@ pub(crate) mod tests {
         let target = target.unwrap().1;
         assert_eq!(target.entries.len(), 21);
     }
+
+    fn ascii_dr_record(leader: &str, directory: &str) -> ReadResult<(Directory, Vec<u8>)> {
+        let bytes = [
+            leader.as_bytes(),
+            directory.as_bytes(),
+            &[FIELD_TERMINATOR],
+            "##abc".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "xy".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let buffer = Cursor::new(bytes);
+        let bufreader = BufReader::new(buffer);
+        let mut reader = Reader::new(bufreader);
+
+        let leader = Leader::read_dr(&mut reader, &ReaderOptions::strict())?;
+        let directory = Directory::read(&mut reader, &leader)?;
+        let field_area = directory.read_field_area(&mut reader, &leader)?;
+        Ok((directory, field_area))
+    }
+
+    #[test]
+    fn test_field_area() {
+        let (directory, field_area) =
+            ascii_dr_record("00062 D     00055   5504", "00010000300004FRID0000400000").unwrap();
+        assert_eq!(field_area.len(), 7);
+
+        let entries = directory.entries();
+        assert_eq!(
+            entries[0].field_data(&field_area).unwrap(),
+            &[b'x', b'y', FIELD_TERMINATOR]
+        );
+        assert_eq!(
+            entries[1].field_data(&field_area).unwrap(),
+            &[b'a', b'b', b'c', FIELD_TERMINATOR]
+        );
+
+        // a field length that does not end on the field terminator
+        let (directory, field_area) =
+            ascii_dr_record("00062 D     00055   5504", "00010000300004FRID0000300000").unwrap();
+        let entries = directory.entries();
+        assert!(entries[0].field_data(&field_area).is_ok());
+        assert!(entries[1].field_data(&field_area).is_err());
+
+        // a field beyond the end of the field area
+        let (directory, field_area) =
+            ascii_dr_record("00062 D     00055   5504", "00010000300005FRID0000400000").unwrap();
+        assert!(directory.entries()[0].field_data(&field_area).is_err());
+
+        // a base address inside the directory
+        assert!(
+            ascii_dr_record("00062 D     00050   5504", "00010000300004FRID0000400000").is_err()
+        );
+    }
+
+    #[test]
+    fn test_large_records() {
+        // the record length is left as zero
+        let (directory, field_area) =
+            ascii_dr_record("00000 D     00055   5504", "00010000300004FRID0000400000").unwrap();
+        assert_eq!(field_area.len(), 7);
+        assert_eq!(
+            directory.entries()[1].field_data(&field_area).unwrap(),
+            &[b'a', b'b', b'c', FIELD_TERMINATOR]
+        );
+
+        // positions wrapping around in a single digit
+        let bytes = [
+            "00000 D     00034   1101A70B57C12".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let leader = Leader::read_dr(&mut reader, &ReaderOptions::strict()).unwrap();
+        match Directory::read(&mut reader, &leader) {
+            Err(ReadError::ParseError(e)) => assert!(e.contains("overflows"), "{}", e),
+            e => panic!("{:?}", e),
+        }
+
+        // a gap as long as the length limit is left alone when the record length is given
+        let directory = ["0001100FRID111".as_bytes(), &[FIELD_TERMINATOR]].concat();
+        let bytes = ["00051 D     00039   1204".as_bytes(), &directory].concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let leader = Leader::read_dr(&mut reader, &ReaderOptions::strict()).unwrap();
+        assert!(Directory::read(&mut reader, &leader).is_ok());
+
+        let bytes = ["00000 D     00039   1204".as_bytes(), &directory].concat();
+        let mut reader = Reader::new(Cursor::new(bytes));
+        let leader = Leader::read_dr(&mut reader, &ReaderOptions::strict()).unwrap();
+        assert!(Directory::read(&mut reader, &leader).is_err());
+    }
+
+    #[test]
+    fn test_large_data_records() {
+        let count = 15_000;
+        let coordinates: Vec<u8> = (0..count as i32)
+            .flat_map(|i| [i.to_le_bytes(), (-i).to_le_bytes()].concat())
+            .collect();
+        // six digit field lengths and positions with the record length left as zero
+        let bytes = [
+            "001683LE1 0900058 ! 3404".as_bytes(),
+            "0000019000000010430019SG2D0480062".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001SG2D".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0500;&   ISO 8211 Record Identifier".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR],
+            "(b12)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "2500;&   2-D coordinate field".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "*YCOO!XCOO".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(2b24)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00000 D     00057   6604".as_bytes(),
+            "0001000003000000SG2D120001000003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            &[0x01, 0x00, FIELD_TERMINATOR],
+            &coordinates,
+            &[FIELD_TERMINATOR],
+            "00059 D     00047   3404".as_bytes(),
+            "00010030000SG2D0090003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            &[0x02, 0x00, FIELD_TERMINATOR],
+            &[0; 8],
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target = DataDescriptiveFile::from_bytes(&bytes).unwrap();
+
+        let data_records = target.data_records();
+        assert_eq!(data_records.len(), 2);
+        assert_eq!(*data_records[0].leader().record_length(), 0);
+        let sg2d = data_records[0].data_field("SG2D").unwrap();
+        assert_eq!(sg2d.row_count(), count);
+        let last = sg2d.rows().last().unwrap();
+        assert_eq!(last.get_i32("YCOO"), Some(count as i32 - 1));
+        assert_eq!(last.get_i32("XCOO"), Some(1 - count as i32));
+        assert_eq!(
+            data_records[1].record_identifier(),
+            Some(&RecordIdentifier::Number(2))
+        );
+    }
 }
@@ -1,7 +1,7 @@
-use crate::{leader::Leader, ReadResult, Reader};
-use std::io::{Read, Seek};
+use crate::{error::ReadError, leader::Leader, ReadResult, Reader, FIELD_TERMINATOR};
+use std::io::Read;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DirectoryEntry {
     field_length: u64,
     field_position: u64,
@@ -9,10 +9,7 @@ pub struct DirectoryEntry {
 }
 
 impl DirectoryEntry {
-    pub fn read<T: Read + Seek>(
-        reader: &mut Reader<T>,
-        leader: &Leader,
-    ) -> ReadResult<DirectoryEntry> {
+    pub fn read<T: Read>(reader: &mut Reader<T>, leader: &Leader) -> ReadResult<DirectoryEntry> {
         let entry_map = leader.entry_map();
         let field_tag = reader.read_str(*entry_map.field_tag() as usize)?;
         let field_length = reader.read_u64_str(*entry_map.field_length() as usize)?;
@@ -24,6 +21,38 @@ impl DirectoryEntry {
         })
     }
 
+    /// The bytes of the field within the field area, ending with its field terminator
+    pub fn field_data<'a>(&self, field_area: &'a [u8]) -> ReadResult<&'a [u8]> {
+        self.terminated_field_data(field_area, &[FIELD_TERMINATOR])
+    }
+
+    /// The bytes of the field within the field area, ending with the given field terminator
+    pub fn terminated_field_data<'a>(
+        &self,
+        field_area: &'a [u8],
+        field_terminator: &[u8],
+    ) -> ReadResult<&'a [u8]> {
+        let start = self.field_position as usize;
+        let end = start + self.field_length as usize;
+        if self.field_length == 0 || end > field_area.len() {
+            return Err(ReadError::ParseError(format!(
+                "Field {} at position {} with length {} is outside the field area of {} bytes",
+                self.field_tag,
+                self.field_position,
+                self.field_length,
+                field_area.len()
+            )));
+        }
+        if !field_area[start..end].ends_with(field_terminator) {
+            return Err(ReadError::ParseError(format!(
+                "Did not find a field terminator at position {} for the Field {}",
+                end - 1,
+                self.field_tag
+            )));
+        }
+        Ok(&field_area[start..end])
+    }
+
     pub fn field_length(&self) -> &u64 {
         &self.field_length
     }
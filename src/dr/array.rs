@@ -0,0 +1,104 @@
+/// An n-dimensional array stored in row-major order
+#[derive(Debug, PartialEq, Clone)]
+pub struct Array<T> {
+    shape: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T> Array<T> {
+    /// Creates an array, the number of values must match the product of the shape
+    pub fn new(shape: Vec<usize>, values: Vec<T>) -> Option<Array<T>> {
+        if shape.iter().product::<usize>() != values.len() {
+            return None;
+        }
+        Some(Array { shape, values })
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The number of dimensions
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The flat row-major offset of an index, `None` when it is out of bounds
+    pub fn offset(&self, index: &[usize]) -> Option<usize> {
+        if index.len() != self.shape.len() {
+            return None;
+        }
+        let mut offset = 0;
+        for (i, extent) in index.iter().zip(self.shape.iter()) {
+            if i >= extent {
+                return None;
+            }
+            offset = offset * extent + i;
+        }
+        Some(offset)
+    }
+
+    /// The value at a row-major index such as `[row, column]`
+    pub fn get(&self, index: &[usize]) -> Option<&T> {
+        self.offset(index).map(|offset| &self.values[offset])
+    }
+
+    /// Converts each value, keeping the shape
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Array<U> {
+        Array {
+            shape: self.shape.clone(),
+            values: self.values.iter().map(f).collect(),
+        }
+    }
+
+    /// Converts each value, keeping the shape, or returns `None` if any conversion fails
+    pub fn try_map<U, F: FnMut(&T) -> Option<U>>(&self, f: F) -> Option<Array<U>> {
+        let values: Option<Vec<U>> = self.values.iter().map(f).collect();
+        values.map(|values| Array {
+            shape: self.shape.clone(),
+            values,
+        })
+    }
+
+    /// The flat row-major values and the shape
+    pub fn into_vec(self) -> (Vec<T>, Vec<usize>) {
+        (self.values, self.shape)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::dr::Array;
+
+    #[test]
+    fn test_array() {
+        let target = Array::new(vec![2, 3], (0..6).collect::<Vec<i32>>()).unwrap();
+        assert_eq!(target.ndim(), 2);
+        assert_eq!(target.len(), 6);
+        assert_eq!(target.get(&[0, 2]), Some(&2));
+        assert_eq!(target.get(&[1, 0]), Some(&3));
+        assert_eq!(target.get(&[2, 0]), None);
+        assert_eq!(target.get(&[1]), None);
+
+        let target = target.map(|v| *v as f64 * 0.5);
+        assert_eq!(target.get(&[1, 2]), Some(&2.5));
+
+        let (values, shape) = target.into_vec();
+        assert_eq!(values.len(), 6);
+        assert_eq!(shape, vec![2, 3]);
+
+        assert!(Array::new(vec![2, 2], vec![1, 2, 3]).is_none());
+    }
+}
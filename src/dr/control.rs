@@ -0,0 +1,81 @@
+use crate::{dr::SubfieldRef, error::ReadError, ReadResult};
+use std::fmt;
+
+/// The identifier of a data record, read from its `0001` field
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum RecordIdentifier {
+    Number(u64),
+    Text(String),
+}
+
+impl RecordIdentifier {
+    pub(crate) fn from_value(value: &SubfieldRef) -> ReadResult<RecordIdentifier> {
+        if let Some(number) = value.as_u64() {
+            return Ok(RecordIdentifier::Number(number));
+        }
+        match value.as_str().map(str::trim) {
+            // character identifiers are often written as digits
+            Some(text) => match text.parse::<u64>() {
+                Ok(number) => Ok(RecordIdentifier::Number(number)),
+                Err(_) if !text.is_empty() => Ok(RecordIdentifier::Text(String::from(text))),
+                Err(_) => Err(ReadError::ParseError(String::from(
+                    "Empty Record Identifier",
+                ))),
+            },
+            None => Err(ReadError::ParseError(format!(
+                "Invalid Record Identifier: {:?}",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            RecordIdentifier::Number(number) => Some(*number),
+            RecordIdentifier::Text(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            RecordIdentifier::Number(_) => None,
+            RecordIdentifier::Text(text) => Some(text),
+        }
+    }
+}
+
+impl fmt::Display for RecordIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordIdentifier::Number(number) => write!(f, "{}", number),
+            RecordIdentifier::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::dr::{RecordIdentifier, SubfieldRef, SubfieldValue};
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_record_identifier() {
+        let values = [
+            SubfieldValue::UnsignedInteger(12),
+            SubfieldValue::String(String::from("  42")),
+            SubfieldValue::String(String::from("A12")),
+        ];
+        let targets: Vec<RecordIdentifier> = values
+            .iter()
+            .map(|value| RecordIdentifier::from_value(&value.as_subfield_ref()).unwrap())
+            .collect();
+
+        assert_eq!(targets[0], RecordIdentifier::Number(12));
+        assert_eq!(targets[1].as_u64(), Some(42));
+        assert_eq!(targets[2].as_str(), Some("A12"));
+        assert_eq!(targets[2].to_string(), "A12");
+
+        assert!(RecordIdentifier::from_value(&SubfieldRef::Empty).is_err());
+        assert!(RecordIdentifier::from_value(&SubfieldRef::String(Cow::Borrowed(""))).is_err());
+    }
+}
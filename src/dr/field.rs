@@ -1,13 +1,765 @@
-use crate::{dr::DirectoryEntry, ReadResult, Reader};
-use std::io::{Read, Seek};
+use crate::{
+    ddr::{ArrayDescriptor, DataDescriptiveField, DataStructure, DataType, Format, FormatCode},
+    dr::{Array, DirectoryEntry, SubfieldRef, SubfieldRow, SubfieldValue},
+    error::ReadError,
+    ReadResult,
+};
 
-pub struct DataField {}
+#[derive(Debug)]
+pub struct DataField {
+    tag: String,
+    labels: Vec<String>,
+    repeat_start: Option<usize>,
+    array_shape: Option<Vec<usize>>,
+    /// the number of labels and values of each sub-structure of a concatenated field
+    structure_lengths: Vec<(usize, usize)>,
+    values: Vec<SubfieldValue>,
+}
 
 impl DataField {
-    pub fn read<T: Read + Seek>(
-        _reader: &mut Reader<T>,
-        _entry: &DirectoryEntry,
+    /// Decodes the bytes of a field, as found in the field area at the position of its entry
+    pub fn read(
+        entry: &DirectoryEntry,
+        data: &[u8],
+        data_descriptive_field: &DataDescriptiveField,
+    ) -> ReadResult<DataField> {
+        DataField::read_tagged(entry.field_tag(), data, data_descriptive_field)
+    }
+
+    pub(crate) fn read_tagged(
+        tag: &str,
+        data: &[u8],
+        data_descriptive_field: &DataDescriptiveField,
     ) -> ReadResult<DataField> {
-        Ok(DataField {})
+        let tag = String::from(tag);
+
+        let array_descriptor = data_descriptive_field.array_descriptor();
+        let labels = array_descriptor.labels().to_vec();
+        let repeat_start = array_descriptor.repeat_start();
+        let values: Vec<SubfieldValue> =
+            DataField::decode_values(&tag, data, data_descriptive_field)?
+                .into_iter()
+                .map(SubfieldRef::into_owned)
+                .collect();
+
+        if let Some(start) = repeat_start {
+            if start < labels.len() && values.len() > start {
+                let width = labels.len() - start;
+                if (values.len() - start) % width != 0 {
+                    return Err(ReadError::ParseError(format!(
+                        "Data Field {} ends with an incomplete repetition of {} subfields",
+                        tag, width
+                    )));
+                }
+            }
+        }
+
+        let array_shape = match data_descriptive_field.field_controls().data_structure() {
+            DataStructure::MultiDimensionalStructure => Some(DataField::array_shape(
+                &tag,
+                array_descriptor,
+                values.len(),
+            )?),
+            _ => None,
+        };
+
+        let structure_lengths = match array_descriptor {
+            ArrayDescriptor::Concatenated { parts, .. } => parts
+                .iter()
+                .map(|part| (part.labels().len(), part.subfield_count()))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(DataField {
+            tag,
+            labels,
+            repeat_start,
+            array_shape,
+            structure_lengths,
+            values,
+        })
+    }
+
+    /// Works out the shape of a multi-dimensional field from its array descriptor, a repeating
+    /// descriptor adds a leading dimension for the repetitions
+    fn array_shape(
+        tag: &str,
+        array_descriptor: &ArrayDescriptor,
+        count: usize,
+    ) -> ReadResult<Vec<usize>> {
+        let (shape, expected) = match array_descriptor {
+            ArrayDescriptor::Elementary => (vec![count], count),
+            ArrayDescriptor::Vector {
+                labels,
+                repeat_start: Some(start),
+            } => {
+                let width = labels.len() - start;
+                let rows = count.saturating_sub(*start) / width;
+                (vec![rows, width], start + rows * width)
+            }
+            ArrayDescriptor::Vector { labels, .. } => (vec![labels.len()], labels.len()),
+            _ => {
+                let mut shape = array_descriptor.shape().unwrap_or_default();
+                let size: usize = shape.iter().product();
+                if array_descriptor.repeat_start().is_some() && size > 0 {
+                    shape.insert(0, count / size);
+                }
+                let expected = shape.iter().product();
+                (shape, expected)
+            }
+        };
+        if expected != count {
+            return Err(ReadError::ParseError(format!(
+                "Data Field {} has {} values but its array shape {:?} requires {}",
+                tag, count, shape, expected
+            )));
+        }
+        Ok(shape)
+    }
+
+    /// Decodes the bytes of a field, ending with its field terminator, into subfield values
+    /// that borrow from the bytes
+    pub(crate) fn decode_values<'a>(
+        tag: &str,
+        data: &'a [u8],
+        data_descriptive_field: &DataDescriptiveField,
+    ) -> ReadResult<Vec<SubfieldRef<'a>>> {
+        // it should all end with a field terminator here
+        let field_terminator = data_descriptive_field
+            .field_controls()
+            .escape_sequence()
+            .field_terminator();
+        if !data.ends_with(field_terminator) {
+            return Err(ReadError::ParseError(format!(
+                "Did not find a field terminator after the Data Field: {}",
+                tag
+            )));
+        }
+        let data = &data[..data.len() - field_terminator.len()];
+        DataField::decode(data, data_descriptive_field)
+    }
+
+    /// Decodes the field data into subfield values, reusing the format controls until the data
+    /// is exhausted so that repeating fields are read completely
+    fn decode<'a>(
+        data: &'a [u8],
+        data_descriptive_field: &DataDescriptiveField,
+    ) -> ReadResult<Vec<SubfieldRef<'a>>> {
+        let formats = data_descriptive_field.format_controls().formats();
+        let character_only = data_descriptive_field.is_character_only();
+        let lexical_level = data_descriptive_field.field_controls().escape_sequence();
+        if formats.is_empty() {
+            let decode = |data: &'a [u8]| {
+                if character_only {
+                    return SubfieldRef::decode_string(data, lexical_level);
+                }
+                match data_descriptive_field.field_controls().data_type() {
+                    DataType::CharacterString => SubfieldRef::decode_string(data, lexical_level),
+                    DataType::ImplicitPoint => SubfieldRef::decode_implicit_point(data),
+                    DataType::ExplicitPoint | DataType::ExplicitPointScaled => {
+                        SubfieldRef::decode_explicit_point(data)
+                    }
+                    DataType::CharacterBitString => SubfieldRef::decode_character_bit_string(data),
+                    _ => Ok(SubfieldRef::Bytes(data)),
+                }
+            };
+            // without format controls the subfields of a vector are delimited
+            let array_descriptor = data_descriptive_field.array_descriptor();
+            if array_descriptor.labels().len() > 1 || array_descriptor.repeat_start().is_some() {
+                let unit_terminator = lexical_level.unit_terminator();
+                let mut units: Vec<&[u8]> = Vec::new();
+                let mut remaining = data;
+                while !remaining.is_empty() {
+                    match find_terminator(remaining, unit_terminator) {
+                        Some(end) => {
+                            units.push(&remaining[..end]);
+                            remaining = &remaining[end + unit_terminator.len()..];
+                        }
+                        None => {
+                            units.push(remaining);
+                            remaining = &[];
+                        }
+                    }
+                }
+                return units.into_iter().map(decode).collect();
+            }
+            return Ok(vec![decode(data)?]);
+        }
+
+        // formats that match the labels one to one also repeat from the repeating part
+        let array_descriptor = data_descriptive_field.array_descriptor();
+        let repeat_start = if formats.len() == array_descriptor.labels().len() {
+            array_descriptor.repeat_start().unwrap_or(0)
+        } else {
+            0
+        };
+
+        let mut values: Vec<SubfieldRef<'a>> = Vec::with_capacity(DataField::subfield_count(
+            data.len(),
+            &formats,
+            repeat_start,
+        ));
+        let mut position = 0;
+        let mut index = 0;
+        while position < data.len() {
+            let format = &formats[cycle_index(index, formats.len(), repeat_start)];
+            index += 1;
+
+            let remaining = &data[position..];
+            let width = match format.code() {
+                FormatCode::CharacterData => format
+                    .byte_width()
+                    .map(|width| width * lexical_level.character_width()),
+                _ => format.byte_width(),
+            };
+            let subfield = match width {
+                Some(width) => {
+                    if width > remaining.len() {
+                        return Err(ReadError::ParseError(format!(
+                            "Subfield {} of width {} overruns the Data Field",
+                            format, width
+                        )));
+                    }
+                    position += width;
+                    &remaining[..width]
+                }
+                None => match find_terminator(remaining, lexical_level.unit_terminator()) {
+                    Some(end) => {
+                        position += end + lexical_level.unit_terminator().len();
+                        &remaining[..end]
+                    }
+                    None => {
+                        position += remaining.len();
+                        remaining
+                    }
+                },
+            };
+            match format.code() {
+                FormatCode::Skip => {}
+                _ if character_only => {
+                    values.push(SubfieldRef::decode_string(subfield, lexical_level)?)
+                }
+                _ => values.push(SubfieldRef::decode_with_level(
+                    format,
+                    subfield,
+                    lexical_level,
+                )?),
+            }
+        }
+        Ok(values)
+    }
+
+    /// Works out the number of subfields from the field length when all formats have fixed
+    /// widths, otherwise the count of the formats
+    fn subfield_count(length: usize, formats: &[Format], repeat_start: usize) -> usize {
+        let widths: Option<Vec<usize>> = formats.iter().map(Format::byte_width).collect();
+        let widths = match widths {
+            Some(widths) => widths,
+            None => return formats.len(),
+        };
+        let prefix: usize = widths[..repeat_start].iter().sum();
+        let row: usize = widths[repeat_start..].iter().sum();
+        if row == 0 || length < prefix {
+            return formats.len();
+        }
+        repeat_start + (length - prefix) / row * (formats.len() - repeat_start)
+    }
+
+    pub fn tag(&self) -> &String {
+        &self.tag
+    }
+
+    /// The subfield labels from the array descriptor of the field
+    pub fn labels(&self) -> &Vec<String> {
+        &self.labels
+    }
+
+    pub fn values(&self) -> &Vec<SubfieldValue> {
+        &self.values
+    }
+
+    /// The label of the subfield value at the given index
+    pub fn label(&self, index: usize) -> Option<&str> {
+        if index < self.labels.len() {
+            return Some(&self.labels[index]);
+        }
+        match self.repeat_start {
+            Some(start) if start < self.labels.len() => {
+                Some(&self.labels[cycle_index(index, self.labels.len(), start)])
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterates over the `(label, value)` pairs of the field
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SubfieldValue)> {
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (self.label(i).unwrap_or_default(), value))
+    }
+
+    /// The first value with the given label
+    pub fn get(&self, label: &str) -> Option<&SubfieldValue> {
+        self.iter()
+            .find(|(l, _)| *l == label)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_str(&self, label: &str) -> Option<&str> {
+        self.get(label).and_then(SubfieldValue::as_str)
+    }
+
+    pub fn get_bytes(&self, label: &str) -> Option<&[u8]> {
+        self.get(label).and_then(SubfieldValue::as_bytes)
+    }
+
+    pub fn get_u8(&self, label: &str) -> Option<u8> {
+        self.get(label).and_then(SubfieldValue::as_u8)
+    }
+
+    pub fn get_u16(&self, label: &str) -> Option<u16> {
+        self.get(label).and_then(SubfieldValue::as_u16)
+    }
+
+    pub fn get_u32(&self, label: &str) -> Option<u32> {
+        self.get(label).and_then(SubfieldValue::as_u32)
+    }
+
+    pub fn get_u64(&self, label: &str) -> Option<u64> {
+        self.get(label).and_then(SubfieldValue::as_u64)
+    }
+
+    pub fn get_i32(&self, label: &str) -> Option<i32> {
+        self.get(label).and_then(SubfieldValue::as_i32)
+    }
+
+    pub fn get_i64(&self, label: &str) -> Option<i64> {
+        self.get(label).and_then(SubfieldValue::as_i64)
+    }
+
+    pub fn get_f64(&self, label: &str) -> Option<f64> {
+        self.get(label).and_then(SubfieldValue::as_f64)
+    }
+
+    /// The values of a multi-dimensional field as an array, `None` for other data structures
+    pub fn array(&self) -> Option<Array<SubfieldValue>> {
+        let shape = self.array_shape.as_ref()?;
+        let start = self.values.len() - shape.iter().product::<usize>();
+        Array::new(shape.clone(), self.values[start..].to_vec())
+    }
+
+    /// The values of each sub-structure of a concatenated field, empty for other data
+    /// structures. A repeating last sub-structure holds its first repetition, all of its
+    /// repetitions are available from `rows`.
+    pub fn structures(&self) -> Vec<SubfieldRow<'_>> {
+        let mut structures = Vec::with_capacity(self.structure_lengths.len());
+        let mut label_start = 0;
+        let mut start = 0;
+        for (labels, values) in &self.structure_lengths {
+            let end = (start + values).min(self.values.len());
+            structures.push(SubfieldRow::new(
+                &self.labels[label_start..label_start + labels],
+                &self.values[start..end],
+            ));
+            label_start += labels;
+            start = end;
+        }
+        structures
+    }
+
+    /// Whether the array descriptor of the field has a repeating part
+    pub fn is_repeating(&self) -> bool {
+        self.repeat_start.is_some()
+    }
+
+    /// The values before the repeating part, or all values if the field does not repeat
+    pub fn prefix(&self) -> SubfieldRow<'_> {
+        let end = self
+            .repeat_start
+            .unwrap_or(self.labels.len())
+            .min(self.values.len());
+        SubfieldRow::new(
+            &self.labels[..end.min(self.labels.len())],
+            &self.values[..end],
+        )
+    }
+
+    /// Iterates over each repetition of the repeating part of the field
+    pub fn rows(&self) -> impl Iterator<Item = SubfieldRow<'_>> {
+        let (labels, values): (&[String], &[SubfieldValue]) = match self.repeat_start {
+            Some(start) if start < self.labels.len() && start <= self.values.len() => {
+                (&self.labels[start..], &self.values[start..])
+            }
+            _ => (&[], &[]),
+        };
+        values
+            .chunks(labels.len().max(1))
+            .map(move |values| SubfieldRow::new(labels, values))
+    }
+
+    /// The number of repetitions of the repeating part of the field
+    pub fn row_count(&self) -> usize {
+        match self.repeat_start {
+            Some(start) if start < self.labels.len() && start <= self.values.len() => {
+                (self.values.len() - start) / (self.labels.len() - start)
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// The position of the first terminator in the data, two byte terminators are only found at
+/// even positions so that they are not confused with the halves of two characters
+fn find_terminator(data: &[u8], terminator: &[u8]) -> Option<usize> {
+    data.chunks_exact(terminator.len())
+        .position(|chunk| chunk == terminator)
+        .map(|index| index * terminator.len())
+}
+
+/// Maps an index onto a sequence of `length` items whose tail from `start` repeats
+fn cycle_index(index: usize, length: usize, start: usize) -> usize {
+    if index < length {
+        index
+    } else {
+        start + (index - length) % (length - start)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::ddr::ascii_data_descriptive_field;
+    use crate::directory::tests::ascii_ddr_directory;
+    use crate::leader::tests::ascii_dr_leader;
+    use crate::{
+        ddr::ArrayDescriptor,
+        dr::{DataField, Directory, SubfieldRow, SubfieldValue},
+        DataDescriptiveFile, ReadResult, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR,
+    };
+    use std::io::{BufReader, Cursor};
+
+    pub fn ascii_dr_directory() -> ReadResult<Directory> {
+        let leader = ascii_dr_leader(0)?;
+
+        let bytes = [
+            "00010000300000FRID0001300003ATTF0001000016AR2D0002900026CATT0001500055".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let buffer = Cursor::new(bytes);
+        let bufreader = BufReader::new(buffer);
+        let mut reader = Reader::new(bufreader);
+
+        Directory::read(&mut reader, &leader)
+    }
+
+    pub fn ascii_data_field(index: usize) -> ReadResult<DataField> {
+        let ddr_directory = ascii_ddr_directory()?.1;
+        let directory = ascii_dr_directory()?;
+
+        let bytes = [
+            [&[0x01, 0x00], &[FIELD_TERMINATOR][..]].concat(),
+            [
+                &[0x64, 0x01, 0x00, 0x00, 0x00, 0x01, 0x02, 0x03][..],
+                &[0x00, 0x01, 0x00, 0x01],
+                &[FIELD_TERMINATOR],
+            ]
+            .concat(),
+            [
+                &[0x74, 0x00][..],
+                "1".as_bytes(),
+                &[UNIT_TERMINATOR],
+                &[0x75, 0x00],
+                "24".as_bytes(),
+                &[UNIT_TERMINATOR],
+                &[FIELD_TERMINATOR],
+            ]
+            .concat(),
+            [
+                (1..=7)
+                    .flat_map(|i: i32| (i * 10).to_le_bytes().to_vec())
+                    .collect::<Vec<u8>>()
+                    .as_slice(),
+                &[FIELD_TERMINATOR],
+            ]
+            .concat(),
+            [
+                &[0x64, 0x01, 0x00, 0x00, 0x00, 0x74, 0x00][..],
+                "1".as_bytes(),
+                &[UNIT_TERMINATOR],
+                &[0x75, 0x00],
+                "24".as_bytes(),
+                &[UNIT_TERMINATOR],
+                &[FIELD_TERMINATOR],
+            ]
+            .concat(),
+        ];
+        let ddf_index = [0, 1, 3, 17, 20];
+        let ddf = ascii_data_descriptive_field(ddf_index[index], &ddr_directory)?;
+
+        DataField::read(&directory.entries()[index], &bytes[index], &ddf)
+    }
+
+    #[test]
+    fn test_data_fields() {
+        let target = ascii_data_field(0).unwrap();
+        assert_eq!(target.tag(), "0001");
+        assert_eq!(target.values(), &vec![SubfieldValue::UnsignedInteger(1)]);
+        assert_eq!(target.label(0), None);
+
+        let target = ascii_data_field(1).unwrap();
+        assert_eq!(target.tag(), "FRID");
+        assert_eq!(target.values().len(), 7);
+        assert_eq!(target.get_u8("RCNM"), Some(100));
+        assert_eq!(target.get_u32("RCID"), Some(1));
+        assert_eq!(target.get_u16("OBJL"), Some(3));
+        assert_eq!(target.get("RUIN"), Some(&SubfieldValue::UnsignedInteger(1)));
+        assert_eq!(target.get("ATTL"), None);
+
+        let target = ascii_data_field(2).unwrap();
+        assert_eq!(target.tag(), "ATTF");
+        assert_eq!(target.values().len(), 4);
+        assert_eq!(target.get_u16("ATTL"), Some(116));
+        assert_eq!(target.get_str("ATVL"), Some("1"));
+        let pairs: Vec<(&str, &SubfieldValue)> = target.iter().collect();
+        assert_eq!(pairs[2], ("ATTL", &SubfieldValue::UnsignedInteger(117)));
+        assert_eq!(
+            pairs[3],
+            ("ATVL", &SubfieldValue::String(String::from("24")))
+        );
+    }
+
+    #[test]
+    fn test_repeating_data_fields() {
+        let target = ascii_data_field(1).unwrap();
+        assert!(!target.is_repeating());
+        assert_eq!(target.prefix().len(), 7);
+        assert_eq!(target.row_count(), 0);
+        assert_eq!(target.rows().count(), 0);
+
+        let target = ascii_data_field(2).unwrap();
+        assert!(target.is_repeating());
+        assert!(target.prefix().is_empty());
+        assert_eq!(target.row_count(), 2);
+        let rows: Vec<SubfieldRow> = target.rows().collect();
+        assert_eq!(rows[0].get_u16("ATTL"), Some(116));
+        assert_eq!(rows[0].get_str("ATVL"), Some("1"));
+        assert_eq!(rows[1].get_u16("ATTL"), Some(117));
+        assert_eq!(rows[1].get_str("ATVL"), Some("24"));
+
+        let target = ascii_data_field(3).unwrap();
+        assert_eq!(target.tag(), "AR2D");
+        let prefix = target.prefix();
+        assert_eq!(prefix.get_i32("STPT"), Some(10));
+        assert_eq!(prefix.get_i32("ENPT"), Some(30));
+        assert_eq!(target.row_count(), 2);
+        let rows: Vec<SubfieldRow> = target.rows().collect();
+        assert_eq!(rows[0].get_i32("YCOO"), Some(40));
+        assert_eq!(rows[1].get_i32("XCOO"), Some(70));
+        assert_eq!(target.label(6), Some("XCOO"));
+    }
+
+    #[test]
+    fn test_concatenated_data_fields() {
+        let target = ascii_data_field(1).unwrap();
+        assert!(target.structures().is_empty());
+
+        let target = ascii_data_field(4).unwrap();
+        assert_eq!(target.tag(), "CATT");
+        let structures = target.structures();
+        assert_eq!(structures.len(), 2);
+        assert_eq!(structures[0].get_u8("RCNM"), Some(100));
+        assert_eq!(structures[0].get_u32("RCID"), Some(1));
+        assert_eq!(structures[1].get_u16("ATTL"), Some(116));
+        assert_eq!(target.row_count(), 2);
+        let rows: Vec<SubfieldRow> = target.rows().collect();
+        assert_eq!(rows[1].get_u16("ATTL"), Some(117));
+        assert_eq!(rows[1].get_str("ATVL"), Some("24"));
+    }
+
+    #[test]
+    fn test_multi_dimensional_data_fields() {
+        let target = ascii_data_field(1).unwrap();
+        assert!(target.array().is_none());
+
+        let target = ascii_data_field(3).unwrap();
+        let array = target.array().unwrap();
+        assert_eq!(array.shape(), &[2, 2]);
+        assert_eq!(array.get(&[0, 1]), Some(&SubfieldValue::SignedInteger(50)));
+        assert_eq!(array.get(&[1, 0]), Some(&SubfieldValue::SignedInteger(60)));
+
+        let (values, shape) = array.try_map(SubfieldValue::as_i32).unwrap().into_vec();
+        assert_eq!(values, vec![40, 50, 60, 70]);
+        assert_eq!(shape, vec![2, 2]);
+
+        let descriptor = ArrayDescriptor::parse("2,3,4").unwrap();
+        assert_eq!(
+            DataField::array_shape("GRID", &descriptor, 12).unwrap(),
+            vec![3, 4]
+        );
+        assert!(DataField::array_shape("GRID", &descriptor, 11).is_err());
+
+        let descriptor = ArrayDescriptor::parse("*A!B\\\\C!D!E").unwrap();
+        assert_eq!(
+            DataField::array_shape("GRID", &descriptor, 18).unwrap(),
+            vec![3, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_ucs_2_data_fields() {
+        let ddr_directory = ascii_ddr_directory().unwrap().1;
+        let entry = &ddr_directory.entries()[4];
+        let ddf = ascii_data_descriptive_field(4, &ddr_directory).unwrap();
+        assert_eq!(ddf.field_name(), "Feature record national attribute field");
+
+        let ucs_2 = |value: &str| -> Vec<u8> {
+            value
+                .encode_utf16()
+                .flat_map(|c| c.to_le_bytes().to_vec())
+                .collect()
+        };
+        // the second byte of the UTF-16LE encoding of "ἀ" is 0x1f, which is not a unit terminator
+        let data = [
+            &[0x2d, 0x01][..],
+            &ucs_2("Øresund"),
+            &[UNIT_TERMINATOR, 0x00],
+            &[0x2e, 0x01],
+            &ucs_2("ἀ"),
+            &[UNIT_TERMINATOR, 0x00],
+            &[FIELD_TERMINATOR, 0x00],
+        ]
+        .concat();
+        let target = DataField::read(entry, &data, &ddf).unwrap();
+        assert_eq!(target.row_count(), 2);
+        let rows: Vec<SubfieldRow> = target.rows().collect();
+        assert_eq!(rows[0].get_u16("ATTL"), Some(301));
+        assert_eq!(rows[0].get_str("ATVL"), Some("Øresund"));
+        assert_eq!(rows[1].get_u16("ATTL"), Some(302));
+        assert_eq!(rows[1].get_str("ATVL"), Some("ἀ"));
+
+        // a single byte field terminator does not end a UCS-2 field
+        let data = [&[0x2d, 0x01][..], &ucs_2("A"), &[FIELD_TERMINATOR]].concat();
+        assert!(DataField::read(entry, &data, &ddf).is_err());
+    }
+
+    #[test]
+    fn test_latin_1_records() {
+        let ddr = [
+            "002303LE1 0900069 ! 3404".as_bytes(),
+            "0000027000000010430027ATTF0590070DSNM0320129".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001ATTF0001DSNM".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0500;&   ISO 8211 Record Identifier".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR],
+            "(b12)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "2600;&-A Feature record attribute field".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "*ATTL!ATVL".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(b12,A)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1600;&   Data set name".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "DSNM".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A)".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        // both names are nine bytes long, so the leader and directory fit either of them
+        let dr = |name: &[u8]| {
+            [
+                "00085 D     00058   3404".as_bytes(),
+                "00010030000ATTF0130003DSNM0110016".as_bytes(),
+                &[FIELD_TERMINATOR],
+                &[0x01, 0x00, FIELD_TERMINATOR],
+                &[116, 0],
+                name,
+                &[UNIT_TERMINATOR, FIELD_TERMINATOR],
+                name,
+                &[UNIT_TERMINATOR, FIELD_TERMINATOR],
+            ]
+            .concat()
+        };
+
+        let target =
+            DataDescriptiveFile::from_bytes(&[ddr.clone(), dr(b"Sao Paulo")].concat()).unwrap();
+        assert_eq!(
+            target.data_records()[0]
+                .data_field("DSNM")
+                .unwrap()
+                .get_str("DSNM"),
+            Some("Sao Paulo")
+        );
+
+        // the accented name is only valid in the ISO 8859-1 field
+        let bytes = [ddr.clone(), dr(b"S\xe3o Paulo")].concat();
+        assert!(DataDescriptiveFile::from_bytes(&bytes).is_err());
+
+        let bytes = [
+            &ddr[..],
+            "00063 D     00047   3404".as_bytes(),
+            "00010030000ATTF0130003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            &[0x01, 0x00, FIELD_TERMINATOR],
+            b"t\x00S\xe3o Paulo",
+            &[UNIT_TERMINATOR, FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target = DataDescriptiveFile::from_bytes(&bytes).unwrap();
+        let attf = target.data_records()[0].data_field("ATTF").unwrap();
+        assert_eq!(attf.get_u16("ATTL"), Some(116));
+        assert_eq!(attf.get_str("ATVL"), Some("São Paulo"));
+    }
+
+    #[test]
+    fn test_ucs_2_records() {
+        let natf = [
+            &[0x2d, 0x01][..],
+            &"Κέρκυρα"
+                .encode_utf16()
+                .flat_map(|c| c.to_le_bytes().to_vec())
+                .collect::<Vec<u8>>(),
+            &[UNIT_TERMINATOR, 0x00],
+            &[FIELD_TERMINATOR, 0x00],
+        ]
+        .concat();
+        // the fields end with their own terminators, two bytes long in the UCS-2 field
+        let bytes = [
+            "001883LE1 0900058 ! 3404".as_bytes(),
+            "0000019000000010430019NATF0680062".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001NATF".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0500;&   ISO 8211 Record Identifier".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR],
+            "(b12)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "2600;&%/@Feature record national attribute field".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "*ATTL!ATVL".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(b12,A)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00070 D     00047   3404".as_bytes(),
+            "00010030000NATF0200003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            &[0x01, 0x00, FIELD_TERMINATOR],
+            &natf,
+        ]
+        .concat();
+        let target = DataDescriptiveFile::from_bytes(&bytes).unwrap();
+
+        let natf = target.data_records()[0].data_field("NATF").unwrap();
+        assert_eq!(natf.get_u16("ATTL"), Some(301));
+        assert_eq!(natf.get_str("ATVL"), Some("Κέρκυρα"));
     }
 }
@@ -1,10 +1,28 @@
 pub use crate::directory::Directory;
 pub use crate::directory_entry::DirectoryEntry;
 
-pub use crate::leader::Leader;
+pub use crate::leader::{EntryMap, Leader};
+
+mod subfield;
+pub use subfield::{SubfieldRef, SubfieldValue};
+
+mod array;
+pub use array::Array;
+
+mod row;
+pub use row::SubfieldRow;
 
 mod field;
 pub use field::DataField;
 
+mod control;
+pub use control::RecordIdentifier;
+
+mod node;
+pub use node::FieldNode;
+
 mod record;
 pub use record::DataRecord;
+
+mod view;
+pub use view::{DataFieldView, DataRecordView};
@@ -0,0 +1,54 @@
+use crate::dr::{DataField, DataRecord};
+
+/// A data field within the field tree of its record
+#[derive(Debug, Clone, Copy)]
+pub struct FieldNode<'a> {
+    record: &'a DataRecord,
+    index: usize,
+}
+
+impl<'a> FieldNode<'a> {
+    pub(crate) fn new(record: &'a DataRecord, index: usize) -> FieldNode<'a> {
+        FieldNode { record, index }
+    }
+
+    /// The position of the field in the record
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn field(&self) -> &'a DataField {
+        &self.record.data_fields()[self.index]
+    }
+
+    pub fn parent(&self) -> Option<FieldNode<'a>> {
+        self.record
+            .parent_index(self.index)
+            .map(|index| FieldNode::new(self.record, index))
+    }
+
+    pub fn children(&self) -> Vec<FieldNode<'a>> {
+        self.record
+            .child_indices(Some(self.index))
+            .map(|index| FieldNode::new(self.record, index))
+            .collect()
+    }
+
+    /// The first child field with the given tag
+    pub fn child(&self, tag: &str) -> Option<FieldNode<'a>> {
+        self.children()
+            .into_iter()
+            .find(|node| node.field().tag() == tag)
+    }
+
+    /// All fields below this one, in record order
+    pub fn descendants(&self) -> Vec<FieldNode<'a>> {
+        let mut descendants: Vec<FieldNode<'a>> = Vec::new();
+        for child in self.children() {
+            descendants.push(child);
+            descendants.extend(child.descendants());
+        }
+        descendants.sort_by_key(|node| node.index);
+        descendants
+    }
+}
@@ -1,29 +1,190 @@
-use crate::{dr::DataField, dr::Directory, dr::Leader, ReadResult, Reader};
-use std::io::{Read, Seek};
+use crate::{
+    ddr::{DataDescriptiveRecord, RECORD_IDENTIFIER_TAG, RECURSIVE_TREE_LINK_TAG},
+    dr::{
+        DataField, DataFieldView, Directory, FieldNode, Leader, RecordIdentifier, SubfieldRef,
+        SubfieldValue,
+    },
+    error::ReadError,
+    ReadResult, Reader, ReaderOptions,
+};
+use std::io::Read;
 
+#[derive(Debug)]
 pub struct DataRecord {
     leader: Leader,
     directory: Directory,
     data_fields: Vec<DataField>,
+    /// the index of the parent of each field in the field tree
+    parents: Vec<Option<usize>>,
+    record_identifier: Option<RecordIdentifier>,
+    tree_links: Vec<RecordIdentifier>,
 }
 
 impl DataRecord {
-    pub fn read<T: Read + Seek>(reader: &mut Reader<T>) -> ReadResult<DataRecord> {
-        let leader = Leader::read_dr(reader)?;
+    pub fn read<T: Read>(
+        reader: &mut Reader<T>,
+        data_descriptive_record: &DataDescriptiveRecord,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataRecord> {
+        let leader = Leader::read_dr(reader, options)?;
 
         let directory = Directory::read(reader, &leader)?;
 
-        let entries = directory.entries();
-        let mut data_fields: Vec<DataField> = Vec::with_capacity(entries.len());
-        for entry in entries {
-            let df = DataField::read(reader, &entry)?;
-            data_fields.push(df);
-        }
+        let field_area = directory.read_field_area(reader, &leader)?;
+
+        DataRecord::decode(leader, directory, &field_area, data_descriptive_record)
+    }
+
+    pub(crate) fn decode(
+        leader: Leader,
+        directory: Directory,
+        field_area: &[u8],
+        data_descriptive_record: &DataDescriptiveRecord,
+    ) -> ReadResult<DataRecord> {
+        let data_fields: Vec<DataField> =
+            DataFieldView::read_all(&directory, field_area, data_descriptive_record)?
+                .iter()
+                .map(DataFieldView::to_data_field)
+                .collect::<ReadResult<_>>()?;
+
+        let tags: Vec<&str> = data_fields.iter().map(|df| df.tag().as_str()).collect();
+        let controls = RecordControls::read(&tags, data_descriptive_record, |index| {
+            Ok(data_fields[index]
+                .values()
+                .iter()
+                .map(SubfieldValue::as_subfield_ref)
+                .collect())
+        })?;
 
         Ok(DataRecord {
             leader,
             directory,
             data_fields,
+            parents: controls.parents,
+            record_identifier: controls.record_identifier,
+            tree_links: controls.tree_links,
+        })
+    }
+
+    pub fn leader(&self) -> &Leader {
+        &self.leader
+    }
+
+    pub fn directory(&self) -> &Directory {
+        &self.directory
+    }
+
+    pub fn data_fields(&self) -> &Vec<DataField> {
+        &self.data_fields
+    }
+
+    /// The identifier from the `0001` field, if the record has one
+    pub fn record_identifier(&self) -> Option<&RecordIdentifier> {
+        self.record_identifier.as_ref()
+    }
+
+    /// The identifiers of the records linked from the `0003` recursive tree link fields
+    pub fn tree_links(&self) -> &Vec<RecordIdentifier> {
+        &self.tree_links
+    }
+
+    /// The first field with the given tag
+    pub fn data_field(&self, tag: &str) -> Option<&DataField> {
+        self.data_fields.iter().find(|df| df.tag() == tag)
+    }
+
+    /// The fields at the top of the field tree, usually the record identifier field
+    pub fn roots(&self) -> Vec<FieldNode<'_>> {
+        self.child_indices(None)
+            .map(|index| FieldNode::new(self, index))
+            .collect()
+    }
+
+    /// The field at the given position within the field tree
+    pub fn field_node(&self, index: usize) -> Option<FieldNode<'_>> {
+        if index < self.data_fields.len() {
+            Some(FieldNode::new(self, index))
+        } else {
+            None
+        }
+    }
+
+    /// The first field with the given tag within the field tree
+    pub fn find_node(&self, tag: &str) -> Option<FieldNode<'_>> {
+        self.data_fields
+            .iter()
+            .position(|df| df.tag() == tag)
+            .map(|index| FieldNode::new(self, index))
+    }
+
+    pub(crate) fn parent_index(&self, index: usize) -> Option<usize> {
+        self.parents[index]
+    }
+
+    pub(crate) fn child_indices(&self, parent: Option<usize>) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .iter()
+            .enumerate()
+            .filter(move |(_, p)| **p == parent)
+            .map(|(index, _)| index)
+    }
+}
+
+/// What the control fields tell about a record, read the same way for records and their views
+pub(crate) struct RecordControls {
+    /// the index of the parent of each field in the field tree
+    pub(crate) parents: Vec<Option<usize>>,
+    pub(crate) record_identifier: Option<RecordIdentifier>,
+    pub(crate) tree_links: Vec<RecordIdentifier>,
+}
+
+impl RecordControls {
+    /// Links the fields with the given tags into the field tree and reads the record identifier
+    /// and tree links from the values of the control fields
+    pub(crate) fn read<'a, F>(
+        tags: &[&str],
+        data_descriptive_record: &DataDescriptiveRecord,
+        values: F,
+    ) -> ReadResult<RecordControls>
+    where
+        F: Fn(usize) -> ReadResult<Vec<SubfieldRef<'a>>>,
+    {
+        let parents = data_descriptive_record.field_tree().link(tags)?;
+
+        // the record identifier field has to come first when present
+        let record_identifier = match tags.iter().position(|tag| *tag == RECORD_IDENTIFIER_TAG) {
+            Some(0) => match values(0)?.first() {
+                Some(value) => Some(RecordIdentifier::from_value(value)?),
+                None => {
+                    return Err(ReadError::ParseError(String::from(
+                        "Empty Record Identifier Field",
+                    )))
+                }
+            },
+            Some(index) => {
+                return Err(ReadError::ParseError(format!(
+                    "Record Identifier Field at position {} instead of first",
+                    index
+                )))
+            }
+            None => None,
+        };
+
+        let mut tree_links: Vec<RecordIdentifier> = Vec::new();
+        for (index, _) in tags
+            .iter()
+            .enumerate()
+            .filter(|(_, tag)| **tag == RECURSIVE_TREE_LINK_TAG)
+        {
+            for value in values(index)?.iter().filter(|value| !value.is_empty()) {
+                tree_links.push(RecordIdentifier::from_value(value)?);
+            }
+        }
+
+        Ok(RecordControls {
+            parents,
+            record_identifier,
+            tree_links,
         })
     }
 }
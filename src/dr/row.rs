@@ -0,0 +1,81 @@
+use crate::dr::SubfieldValue;
+
+/// A set of labelled subfield values, such as one repetition of a repeating field
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SubfieldRow<'a> {
+    labels: &'a [String],
+    values: &'a [SubfieldValue],
+}
+
+impl<'a> SubfieldRow<'a> {
+    pub(crate) fn new(labels: &'a [String], values: &'a [SubfieldValue]) -> SubfieldRow<'a> {
+        SubfieldRow { labels, values }
+    }
+
+    pub fn labels(&self) -> &'a [String] {
+        self.labels
+    }
+
+    pub fn values(&self) -> &'a [SubfieldValue] {
+        self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterates over the `(label, value)` pairs of the row
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a SubfieldValue)> {
+        self.labels
+            .iter()
+            .map(|label| label.as_str())
+            .zip(self.values.iter())
+    }
+
+    /// The value with the given label
+    pub fn get(&self, label: &str) -> Option<&'a SubfieldValue> {
+        self.iter()
+            .find(|(l, _)| *l == label)
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_str(&self, label: &str) -> Option<&'a str> {
+        self.get(label).and_then(SubfieldValue::as_str)
+    }
+
+    pub fn get_bytes(&self, label: &str) -> Option<&'a [u8]> {
+        self.get(label).and_then(SubfieldValue::as_bytes)
+    }
+
+    pub fn get_u8(&self, label: &str) -> Option<u8> {
+        self.get(label).and_then(SubfieldValue::as_u8)
+    }
+
+    pub fn get_u16(&self, label: &str) -> Option<u16> {
+        self.get(label).and_then(SubfieldValue::as_u16)
+    }
+
+    pub fn get_u32(&self, label: &str) -> Option<u32> {
+        self.get(label).and_then(SubfieldValue::as_u32)
+    }
+
+    pub fn get_u64(&self, label: &str) -> Option<u64> {
+        self.get(label).and_then(SubfieldValue::as_u64)
+    }
+
+    pub fn get_i32(&self, label: &str) -> Option<i32> {
+        self.get(label).and_then(SubfieldValue::as_i32)
+    }
+
+    pub fn get_i64(&self, label: &str) -> Option<i64> {
+        self.get(label).and_then(SubfieldValue::as_i64)
+    }
+
+    pub fn get_f64(&self, label: &str) -> Option<f64> {
+        self.get(label).and_then(SubfieldValue::as_f64)
+    }
+}
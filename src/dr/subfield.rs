@@ -0,0 +1,440 @@
+use crate::{
+    ddr::{BinaryForm, Format, FormatCode, LexicalLevel},
+    error::ReadError,
+    ReadResult,
+};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// A decoded subfield value
+#[derive(Debug, PartialEq, Clone)]
+pub enum SubfieldValue {
+    /// Character data
+    String(String),
+    /// Unsigned binary integer
+    UnsignedInteger(u64),
+    /// Signed binary integer or implicit point number
+    SignedInteger(i64),
+    /// Explicit point or binary floating point number
+    Float(f64),
+    /// Bit string, packed with the most significant bit first
+    BitString(Vec<u8>),
+    /// Character mode bit string, one entry per bit
+    Bits(Vec<bool>),
+    /// Data without a numeric or character interpretation
+    Bytes(Vec<u8>),
+    /// Empty numeric subfield
+    Empty,
+}
+
+impl SubfieldValue {
+    /// Decodes the bytes of a single subfield according to its format control, character data
+    /// as the ASCII text of lexical level 0
+    pub fn decode(format: &Format, bytes: &[u8]) -> ReadResult<SubfieldValue> {
+        SubfieldValue::decode_with_level(format, bytes, &LexicalLevel::Level0)
+    }
+
+    /// Decodes the bytes of a single subfield according to its format control, character data
+    /// according to the lexical level of the field
+    pub fn decode_with_level(
+        format: &Format,
+        bytes: &[u8],
+        lexical_level: &LexicalLevel,
+    ) -> ReadResult<SubfieldValue> {
+        Ok(SubfieldRef::decode_with_level(format, bytes, lexical_level)?.into_owned())
+    }
+
+    /// Borrows the value without copying its character and byte data
+    pub fn as_subfield_ref(&self) -> SubfieldRef<'_> {
+        match self {
+            SubfieldValue::String(value) => SubfieldRef::String(Cow::Borrowed(value)),
+            SubfieldValue::UnsignedInteger(value) => SubfieldRef::UnsignedInteger(*value),
+            SubfieldValue::SignedInteger(value) => SubfieldRef::SignedInteger(*value),
+            SubfieldValue::Float(value) => SubfieldRef::Float(*value),
+            SubfieldValue::BitString(value) => SubfieldRef::BitString(value),
+            SubfieldValue::Bits(bits) => SubfieldRef::Bits(Cow::Borrowed(bits)),
+            SubfieldValue::Bytes(value) => SubfieldRef::Bytes(value),
+            SubfieldValue::Empty => SubfieldRef::Empty,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SubfieldValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(&self) -> Option<u8> {
+        self.as_subfield_ref().as_u8()
+    }
+
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_subfield_ref().as_u16()
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_subfield_ref().as_u32()
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_subfield_ref().as_u64()
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_subfield_ref().as_i32()
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_subfield_ref().as_i64()
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_subfield_ref().as_f64()
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            SubfieldValue::String(value) => Some(value.as_bytes()),
+            SubfieldValue::BitString(value) | SubfieldValue::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The bits of a bit string, most significant bit first
+    pub fn as_bits(&self) -> Option<Vec<bool>> {
+        self.as_subfield_ref().as_bits()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_subfield_ref().is_empty()
+    }
+}
+
+/// A decoded subfield value that borrows its character and byte data from the field
+#[derive(Debug, PartialEq, Clone)]
+pub enum SubfieldRef<'a> {
+    /// Character data, only copied when it has to be converted to UTF-8
+    String(Cow<'a, str>),
+    /// Unsigned binary integer
+    UnsignedInteger(u64),
+    /// Signed binary integer or implicit point number
+    SignedInteger(i64),
+    /// Explicit point or binary floating point number
+    Float(f64),
+    /// Bit string, packed with the most significant bit first
+    BitString(&'a [u8]),
+    /// Character mode bit string, one entry per bit
+    Bits(Cow<'a, [bool]>),
+    /// Data without a numeric or character interpretation
+    Bytes(&'a [u8]),
+    /// Empty numeric subfield
+    Empty,
+}
+
+impl<'a> SubfieldRef<'a> {
+    /// Decodes the bytes of a single subfield according to its format control, character data
+    /// as the ASCII text of lexical level 0
+    pub fn decode(format: &Format, bytes: &'a [u8]) -> ReadResult<SubfieldRef<'a>> {
+        SubfieldRef::decode_with_level(format, bytes, &LexicalLevel::Level0)
+    }
+
+    /// Decodes the bytes of a single subfield according to its format control, character data
+    /// according to the lexical level of the field
+    pub fn decode_with_level(
+        format: &Format,
+        bytes: &'a [u8],
+        lexical_level: &LexicalLevel,
+    ) -> ReadResult<SubfieldRef<'a>> {
+        match format.code() {
+            FormatCode::CharacterData | FormatCode::Skip => {
+                SubfieldRef::decode_string(bytes, lexical_level)
+            }
+            FormatCode::ImplicitPoint => SubfieldRef::decode_implicit_point(bytes),
+            FormatCode::ExplicitPoint | FormatCode::ExplicitPointScaled => {
+                SubfieldRef::decode_explicit_point(bytes)
+            }
+            FormatCode::CharacterBitString => SubfieldRef::decode_character_bit_string(bytes),
+            FormatCode::BitString => Ok(SubfieldRef::BitString(bytes)),
+            FormatCode::Binary(form) => SubfieldRef::decode_binary(*form, bytes),
+        }
+    }
+
+    pub(crate) fn decode_string(
+        bytes: &'a [u8],
+        lexical_level: &LexicalLevel,
+    ) -> ReadResult<SubfieldRef<'a>> {
+        Ok(SubfieldRef::String(lexical_level.decode_borrowed(bytes)?))
+    }
+
+    pub(crate) fn decode_implicit_point(bytes: &[u8]) -> ReadResult<SubfieldRef<'a>> {
+        let value = std::str::from_utf8(bytes)
+            .map_err(|e| ReadError::ParseError(e.to_string()))?
+            .trim();
+        if value.is_empty() {
+            return Ok(SubfieldRef::Empty);
+        }
+        Ok(SubfieldRef::SignedInteger(value.parse::<i64>()?))
+    }
+
+    /// Decodes a string of `0` and `1` characters
+    pub(crate) fn decode_character_bit_string(bytes: &[u8]) -> ReadResult<SubfieldRef<'a>> {
+        let bits: Option<Vec<bool>> = bytes
+            .iter()
+            .filter(|b| **b != b' ')
+            .map(|b| match b {
+                b'0' => Some(false),
+                b'1' => Some(true),
+                _ => None,
+            })
+            .collect();
+        match bits {
+            Some(bits) => Ok(SubfieldRef::Bits(Cow::Owned(bits))),
+            None => Err(ReadError::ParseError(format!(
+                "Invalid Character Mode Bit String: {}",
+                String::from_utf8_lossy(bytes)
+            ))),
+        }
+    }
+
+    /// Decodes explicit point numbers, with or without a scale such as `-1.25E+03`
+    pub(crate) fn decode_explicit_point(bytes: &[u8]) -> ReadResult<SubfieldRef<'a>> {
+        let value = std::str::from_utf8(bytes)
+            .map_err(|e| ReadError::ParseError(e.to_string()))?
+            .trim();
+        if value.is_empty() {
+            return Ok(SubfieldRef::Empty);
+        }
+        Ok(SubfieldRef::Float(value.parse::<f64>()?))
+    }
+
+    fn decode_binary(form: BinaryForm, bytes: &'a [u8]) -> ReadResult<SubfieldRef<'a>> {
+        if bytes.is_empty() || bytes.len() > 8 {
+            return Err(ReadError::ParseError(format!(
+                "Invalid Binary Subfield Width: {}",
+                bytes.len()
+            )));
+        }
+
+        let mut buf = [0u8; 8];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        let unsigned = u64::from_le_bytes(buf);
+
+        match form {
+            BinaryForm::UnsignedInteger => Ok(SubfieldRef::UnsignedInteger(unsigned)),
+            BinaryForm::SignedInteger => {
+                let shift = 64 - 8 * bytes.len() as u32;
+                Ok(SubfieldRef::SignedInteger(
+                    ((unsigned << shift) as i64) >> shift,
+                ))
+            }
+            BinaryForm::FloatingPoint => match bytes.len() {
+                4 => Ok(SubfieldRef::Float(f32::from_bits(unsigned as u32) as f64)),
+                8 => Ok(SubfieldRef::Float(f64::from_bits(unsigned))),
+                n => Err(ReadError::ParseError(format!(
+                    "Invalid Floating Point Width: {}",
+                    n
+                ))),
+            },
+            BinaryForm::UnsignedFixedPoint | BinaryForm::SignedFixedPoint => {
+                Ok(SubfieldRef::Bytes(bytes))
+            }
+        }
+    }
+
+    /// Copies the borrowed data into an owned value
+    pub fn into_owned(self) -> SubfieldValue {
+        match self {
+            SubfieldRef::String(value) => SubfieldValue::String(value.into_owned()),
+            SubfieldRef::UnsignedInteger(value) => SubfieldValue::UnsignedInteger(value),
+            SubfieldRef::SignedInteger(value) => SubfieldValue::SignedInteger(value),
+            SubfieldRef::Float(value) => SubfieldValue::Float(value),
+            SubfieldRef::BitString(value) => SubfieldValue::BitString(value.to_vec()),
+            SubfieldRef::Bits(bits) => SubfieldValue::Bits(bits.into_owned()),
+            SubfieldRef::Bytes(value) => SubfieldValue::Bytes(value.to_vec()),
+            SubfieldRef::Empty => SubfieldValue::Empty,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            SubfieldRef::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(&self) -> Option<u8> {
+        self.as_u64().and_then(|value| u8::try_from(value).ok())
+    }
+
+    pub fn as_u16(&self) -> Option<u16> {
+        self.as_u64().and_then(|value| u16::try_from(value).ok())
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_u64().and_then(|value| u32::try_from(value).ok())
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            SubfieldRef::UnsignedInteger(value) => Some(*value),
+            SubfieldRef::SignedInteger(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_i64().and_then(|value| i32::try_from(value).ok())
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            SubfieldRef::UnsignedInteger(value) => i64::try_from(*value).ok(),
+            SubfieldRef::SignedInteger(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            SubfieldRef::UnsignedInteger(value) => Some(*value as f64),
+            SubfieldRef::SignedInteger(value) => Some(*value as f64),
+            SubfieldRef::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            SubfieldRef::String(value) => Some(value.as_bytes()),
+            SubfieldRef::BitString(value) | SubfieldRef::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The bits of a bit string, most significant bit first
+    pub fn as_bits(&self) -> Option<Vec<bool>> {
+        match self {
+            SubfieldRef::BitString(bytes) => Some(
+                bytes
+                    .iter()
+                    .flat_map(|byte| (0..8).rev().map(move |i| byte & (1 << i) != 0))
+                    .collect(),
+            ),
+            SubfieldRef::Bits(bits) => Some(bits.to_vec()),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == SubfieldRef::Empty
+    }
+}
+
+impl<'a> From<SubfieldRef<'a>> for SubfieldValue {
+    fn from(value: SubfieldRef<'a>) -> SubfieldValue {
+        value.into_owned()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        ddr::{FormatControls, LexicalLevel},
+        dr::{SubfieldRef, SubfieldValue},
+    };
+
+    #[test]
+    fn test_decode() {
+        let formats = FormatControls::parse("(b11,b12,b24,b14,b58,A,I,R,I,B(16))")
+            .unwrap()
+            .formats();
+        let bytes: [&[u8]; 10] = [
+            &[0x64],
+            &[0x01, 0x02],
+            &[0xfe, 0xff, 0xff, 0xff],
+            &[0xff, 0xff, 0xff, 0xff],
+            &1.5f64.to_le_bytes(),
+            "GB5X02SE".as_bytes(),
+            " -42".as_bytes(),
+            "2.5".as_bytes(),
+            "".as_bytes(),
+            &[0xab, 0xcd],
+        ];
+        let values: Vec<SubfieldValue> = formats
+            .iter()
+            .zip(bytes.iter())
+            .map(|(format, bytes)| SubfieldValue::decode(format, bytes).unwrap())
+            .collect();
+
+        assert_eq!(values[0], SubfieldValue::UnsignedInteger(100));
+        assert_eq!(values[1], SubfieldValue::UnsignedInteger(0x0201));
+        assert_eq!(values[2], SubfieldValue::SignedInteger(-2));
+        assert_eq!(values[3].as_u64(), Some(0xffff_ffff));
+        assert_eq!(values[4].as_f64(), Some(1.5));
+        assert_eq!(values[5].as_str(), Some("GB5X02SE"));
+        assert_eq!(values[6].as_i64(), Some(-42));
+        assert_eq!(values[7], SubfieldValue::Float(2.5));
+        assert!(values[8].is_empty());
+        assert_eq!(values[9], SubfieldValue::BitString(vec![0xab, 0xcd]));
+    }
+
+    #[test]
+    fn test_decode_scaled_and_bits() {
+        let formats = FormatControls::parse("(S,S(9),C,B(8))").unwrap().formats();
+        let target = SubfieldValue::decode(&formats[0], "-1.25E+03".as_bytes()).unwrap();
+        assert_eq!(target, SubfieldValue::Float(-1250.0));
+        let target = SubfieldValue::decode(&formats[1], "  2.5E-01".as_bytes()).unwrap();
+        assert_eq!(target.as_f64(), Some(0.25));
+
+        let target = SubfieldValue::decode(&formats[2], "1011".as_bytes()).unwrap();
+        assert_eq!(target, SubfieldValue::Bits(vec![true, false, true, true]));
+        let target = SubfieldValue::decode(&formats[3], &[0xa0]).unwrap();
+        assert_eq!(
+            target.as_bits(),
+            Some(vec![true, false, true, false, false, false, false, false])
+        );
+    }
+
+    #[test]
+    fn test_invalid_decode() {
+        let formats = FormatControls::parse("(I,R,b54,C,S)").unwrap().formats();
+        assert!(SubfieldValue::decode(&formats[0], "1x".as_bytes()).is_err());
+        assert!(SubfieldValue::decode(&formats[1], "x".as_bytes()).is_err());
+        assert!(SubfieldValue::decode(&formats[2], &[0, 0, 0]).is_err());
+        assert!(SubfieldValue::decode(&formats[3], "102".as_bytes()).is_err());
+        assert!(SubfieldValue::decode(&formats[4], "1.0F2".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_decode_lexical_levels() {
+        let formats = FormatControls::parse("(A)").unwrap().formats();
+        let bytes = [b'B', b'r', 0xe9, b's', b'i', b'l'];
+
+        let target =
+            SubfieldValue::decode_with_level(&formats[0], &bytes, &LexicalLevel::Level1).unwrap();
+        assert_eq!(target.as_str(), Some("Brésil"));
+        assert!(
+            SubfieldValue::decode_with_level(&formats[0], &bytes, &LexicalLevel::Level0).is_err()
+        );
+        let target =
+            SubfieldValue::decode_with_level(&formats[0], b"Brazil", &LexicalLevel::Level0)
+                .unwrap();
+        assert_eq!(target.as_str(), Some("Brazil"));
+    }
+
+    #[test]
+    fn test_subfield_ref() {
+        let formats = FormatControls::parse("(b11,b24,C,A)").unwrap().formats();
+        let bytes: [&[u8]; 4] = [&[0x64], &[0xfe, 0xff, 0xff, 0xff], b"101", b"GB"];
+        for (format, bytes) in formats.iter().zip(bytes.iter()) {
+            let target = SubfieldRef::decode(format, bytes).unwrap();
+            let value = target.clone().into_owned();
+            assert_eq!(value.as_subfield_ref(), target);
+            assert_eq!(target.as_u8(), value.as_u8());
+            assert_eq!(target.as_i32(), value.as_i32());
+            assert_eq!(target.as_bits(), value.as_bits());
+            assert_eq!(target.as_str(), value.as_str());
+        }
+    }
+}
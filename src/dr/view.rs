@@ -0,0 +1,155 @@
+use crate::{
+    ddr::{DataDescriptiveField, DataDescriptiveRecord},
+    dr::{
+        record::RecordControls, DataField, DataRecord, Directory, Leader, RecordIdentifier,
+        SubfieldRef,
+    },
+    error::ReadError,
+    ReadResult,
+};
+use std::sync::Arc;
+
+/// A data record whose fields borrow their bytes from the file instead of copying them
+#[derive(Debug)]
+pub struct DataRecordView<'a> {
+    /// the leader and directory, shared by the records that reuse them
+    layout: Arc<(Leader, Directory)>,
+    field_area: &'a [u8],
+    data_descriptive_record: &'a DataDescriptiveRecord,
+    data_fields: Vec<DataFieldView<'a>>,
+    record_identifier: Option<RecordIdentifier>,
+    tree_links: Vec<RecordIdentifier>,
+}
+
+impl<'a> DataRecordView<'a> {
+    pub(crate) fn new(
+        layout: Arc<(Leader, Directory)>,
+        field_area: &'a [u8],
+        data_descriptive_record: &'a DataDescriptiveRecord,
+    ) -> ReadResult<DataRecordView<'a>> {
+        let data_fields = DataFieldView::read_all(&layout.1, field_area, data_descriptive_record)?;
+
+        // the same checks as for a record that is read by copying, only the control fields are
+        // decoded
+        let tags: Vec<&str> = data_fields.iter().map(DataFieldView::tag).collect();
+        let controls = RecordControls::read(&tags, data_descriptive_record, |index| {
+            data_fields[index].values()
+        })?;
+
+        Ok(DataRecordView {
+            layout,
+            field_area,
+            data_descriptive_record,
+            data_fields,
+            record_identifier: controls.record_identifier,
+            tree_links: controls.tree_links,
+        })
+    }
+
+    pub fn leader(&self) -> &Leader {
+        &self.layout.0
+    }
+
+    pub fn directory(&self) -> &Directory {
+        &self.layout.1
+    }
+
+    /// The bytes of the field area as found in the file
+    pub fn field_area(&self) -> &'a [u8] {
+        self.field_area
+    }
+
+    pub fn data_fields(&self) -> &Vec<DataFieldView<'a>> {
+        &self.data_fields
+    }
+
+    /// The first field with the given tag
+    pub fn data_field(&self, tag: &str) -> Option<&DataFieldView<'a>> {
+        self.data_fields.iter().find(|df| df.tag() == tag)
+    }
+
+    /// The identifier from the `0001` field, if the record has one
+    pub fn record_identifier(&self) -> Option<&RecordIdentifier> {
+        self.record_identifier.as_ref()
+    }
+
+    /// The identifiers of the records linked from the `0003` recursive tree link fields
+    pub fn tree_links(&self) -> &Vec<RecordIdentifier> {
+        &self.tree_links
+    }
+
+    /// Decodes all fields into an owned record, which also links its field tree
+    pub fn to_data_record(&self) -> ReadResult<DataRecord> {
+        DataRecord::decode(
+            self.layout.0.clone(),
+            self.layout.1.clone(),
+            self.field_area,
+            self.data_descriptive_record,
+        )
+    }
+}
+
+/// The bytes of a data field together with the description needed to decode them
+#[derive(Debug, Clone, Copy)]
+pub struct DataFieldView<'a> {
+    /// the bytes of the field, ending with its field terminator
+    data: &'a [u8],
+    data_descriptive_field: &'a DataDescriptiveField,
+}
+
+impl<'a> DataFieldView<'a> {
+    /// Finds the bytes of each field of the directory within the field area
+    pub(crate) fn read_all(
+        directory: &Directory,
+        field_area: &'a [u8],
+        data_descriptive_record: &'a DataDescriptiveRecord,
+    ) -> ReadResult<Vec<DataFieldView<'a>>> {
+        let entries = directory.entries();
+        let mut data_fields: Vec<DataFieldView<'a>> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let ddf = data_descriptive_record
+                .data_descriptive_field(entry.field_tag())
+                .ok_or_else(|| {
+                    ReadError::ParseError(format!(
+                        "No Data Descriptive Field for tag: {}",
+                        entry.field_tag()
+                    ))
+                })?;
+            let field_terminator = ddf.field_controls().escape_sequence().field_terminator();
+            let data = entry.terminated_field_data(field_area, field_terminator)?;
+            data_fields.push(DataFieldView {
+                data,
+                data_descriptive_field: ddf,
+            });
+        }
+        Ok(data_fields)
+    }
+
+    pub fn tag(&self) -> &'a str {
+        self.data_descriptive_field.tag()
+    }
+
+    /// The bytes of the field without its field terminator
+    pub fn data(&self) -> &'a [u8] {
+        let field_terminator = self
+            .data_descriptive_field
+            .field_controls()
+            .escape_sequence()
+            .field_terminator();
+        &self.data[..self.data.len() - field_terminator.len()]
+    }
+
+    pub fn data_descriptive_field(&self) -> &'a DataDescriptiveField {
+        self.data_descriptive_field
+    }
+
+    /// Decodes the subfield values, borrowing character and byte data from the file
+    pub fn values(&self) -> ReadResult<Vec<SubfieldRef<'a>>> {
+        DataField::decode_values(self.tag(), self.data, self.data_descriptive_field)
+    }
+
+    /// Decodes the field into an owned data field
+    pub fn to_data_field(&self) -> ReadResult<DataField> {
+        DataField::read_tagged(self.tag(), self.data, self.data_descriptive_field)
+    }
+}
@@ -1,5 +1,5 @@
 use std::io::Error;
-use std::num::ParseIntError;
+use std::num::{ParseFloatError, ParseIntError};
 use std::string::FromUtf8Error;
 
 #[derive(Debug)]
@@ -8,6 +8,9 @@ pub enum ReadError {
     IoError(Error),
     Utf8Error(FromUtf8Error),
     IntError(ParseIntError),
+    FloatError(ParseFloatError),
+    /// A character set, given by its escape sequence, that can not be decoded
+    UnsupportedEncoding(String),
 }
 
 impl From<Error> for ReadError {
@@ -34,6 +37,12 @@ impl From<ParseIntError> for ReadError {
     }
 }
 
+impl From<ParseFloatError> for ReadError {
+    fn from(error: ParseFloatError) -> Self {
+        ReadError::FloatError(error)
+    }
+}
+
 // impl From<std::str::Utf8Error> for ReadError {
 //     fn from(error: std::str::Utf8Error) -> Self {
 //         ReadError {
@@ -1,16 +1,16 @@
-use crate::{error::ReadError, ReadResult, Reader};
-use std::io::{Read, Seek};
+use crate::{error::ReadError, ReadResult, Reader, ReaderOptions};
+use std::io::Read;
 
 /*
 RP      Len     Entry name                          Content
 =================================================================================
 0       5       Record length                       number of bytes in record
-5       1       Interchange level                   "3"
+5       1       Interchange level                   "1", "2" or "3"
 6       1       Leader identifier                   "L"
 7       1       In line code extension indicator    "E"
 8       1       Version number                      "1"
 9       1       Application indicator               SPACE
-10      2       Field control length                "09"
+10      2       Field control length                "09", "00" at interchange level 1
 12      5       Base address of field area          Start address of field area (number of bytes inleader and directory)
 17      3       Extended character set indicator    " ! " (SPACE,!,SPACE)
 20      4       Entry map                           (see table below)
@@ -23,8 +23,11 @@ RP      Sub-entry name                  Len     Content
 23      Size of field tag field         1       "4"
 */
 
+/// length of the leader in bytes
+pub(crate) const LEADER_LENGTH: usize = 24;
+
 /// The structure of the DR leader
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Leader {
     /// Record Length
     record_length: u64,
@@ -53,6 +56,34 @@ impl Leader {
         &self.record_length
     }
 
+    pub fn interchange_level(&self) -> &char {
+        &self.interchange_level
+    }
+
+    pub fn leader_identifier(&self) -> &char {
+        &self.leader_identifier
+    }
+
+    pub fn code_extension(&self) -> &char {
+        &self.code_extension
+    }
+
+    pub fn version_number(&self) -> &char {
+        &self.version_number
+    }
+
+    pub fn application_indicator(&self) -> &char {
+        &self.application_indicator
+    }
+
+    pub fn character_set(&self) -> &String {
+        &self.character_set
+    }
+
+    pub fn base_address(&self) -> &u64 {
+        &self.base_address
+    }
+
     pub fn field_control_length(&self) -> &u8 {
         &self.field_control_length
     }
@@ -60,6 +91,11 @@ impl Leader {
     pub fn entry_map(&self) -> &EntryMap {
         &self.entry_map
     }
+
+    /// Whether the following records carry only a field area and reuse this leader and directory
+    pub fn is_reused(&self) -> bool {
+        self.leader_identifier == 'R'
+    }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -75,7 +111,7 @@ pub struct EntryMap {
 }
 
 impl EntryMap {
-    pub fn read<T: Read + Seek>(reader: &mut Reader<T>) -> ReadResult<EntryMap> {
+    pub fn read<T: Read>(reader: &mut Reader<T>) -> ReadResult<EntryMap> {
         let field_length = reader.read_u8_str(1)?;
 
         let field_position = reader.read_u8_str(1)?;
@@ -109,25 +145,41 @@ impl EntryMap {
 }
 
 impl Leader {
-    pub fn read_ddr<T: Read + Seek>(reader: &mut Reader<T>) -> ReadResult<Leader> {
-        Leader::read(reader, true)
+    pub fn read_ddr<T: Read>(
+        reader: &mut Reader<T>,
+        options: &ReaderOptions,
+    ) -> ReadResult<Leader> {
+        Leader::read(reader, true, options)
     }
 
-    pub fn read_dr<T: Read + Seek>(reader: &mut Reader<T>) -> ReadResult<Leader> {
-        Leader::read(reader, false)
+    pub fn read_dr<T: Read>(reader: &mut Reader<T>, options: &ReaderOptions) -> ReadResult<Leader> {
+        Leader::read(reader, false, options)
     }
 
-    fn read<T: Read + Seek>(reader: &mut Reader<T>, is_ddr: bool) -> ReadResult<Leader> {
+    /// Reads the leader, checking it against the S-57 profile in strict mode. In permissive
+    /// mode only the values the rest of the record depends on are checked.
+    fn read<T: Read>(
+        reader: &mut Reader<T>,
+        is_ddr: bool,
+        options: &ReaderOptions,
+    ) -> ReadResult<Leader> {
+        let strict = options.is_strict();
+
         let record_length = reader.read_u64_str(5)?;
         let interchange_level = reader.read_char()?;
-        if (is_ddr && interchange_level != '3') || (!is_ddr && interchange_level != ' ') {
+        if (is_ddr && !['1', '2', '3'].contains(&interchange_level))
+            || (strict && !is_ddr && interchange_level != ' ')
+        {
             return Err(ReadError::ParseError(format!(
                 "Invalid Interchange Level: {}",
                 interchange_level
             )));
         }
         let leader_identifier = reader.read_char()?;
-        if (is_ddr && leader_identifier != 'L') || (!is_ddr && leader_identifier != 'D') {
+        // 'R' marks a record whose leader and directory are reused by the following records
+        if (is_ddr && leader_identifier != 'L')
+            || (!is_ddr && leader_identifier != 'D' && leader_identifier != 'R')
+        {
             return Err(ReadError::ParseError(format!(
                 "Invalid Leader Identifier: {}",
                 leader_identifier
@@ -135,7 +187,7 @@ impl Leader {
         }
 
         let code_extension = reader.read_char()?;
-        if (is_ddr && code_extension != 'E') || (!is_ddr && code_extension != ' ') {
+        if strict && ((is_ddr && code_extension != 'E') || (!is_ddr && code_extension != ' ')) {
             return Err(ReadError::ParseError(format!(
                 "Invalid In Line Code Extension Indicator: {}",
                 code_extension
@@ -143,7 +195,7 @@ impl Leader {
         }
 
         let version_number = reader.read_char()?;
-        if (is_ddr && version_number != '1') || (!is_ddr && version_number != ' ') {
+        if strict && ((is_ddr && version_number != '1') || (!is_ddr && version_number != ' ')) {
             return Err(ReadError::ParseError(format!(
                 "Invalid Verison Number: {}",
                 version_number
@@ -151,33 +203,45 @@ impl Leader {
         }
 
         let application_indicator = reader.read_char()?;
-        if application_indicator != ' ' {
+        if strict && application_indicator != ' ' {
             return Err(ReadError::ParseError(format!(
                 "Invalid Application Indicator: {}",
                 application_indicator
             )));
         }
 
+        // interchange level 1 has no field controls
         let field_control_length_value = reader.read_str(2)?;
-        if (is_ddr && field_control_length_value != "09")
-            || (!is_ddr && field_control_length_value != "  ")
-        {
+        let expected = match (is_ddr, interchange_level) {
+            (true, '1') => "00",
+            (true, _) => "09",
+            (false, _) => "  ",
+        };
+        if strict && field_control_length_value != expected {
             return Err(ReadError::ParseError(format!(
                 "Invalid Field Control Length: {}",
                 field_control_length_value
             )));
         }
-        let field_control_length: u8;
-        if is_ddr {
-            field_control_length = field_control_length_value.parse::<u8>()?;
-        } else {
-            field_control_length = 0;
-        }
+        // the field controls are only used by the DDR, data records may leave this blank. Level 1
+        // files have none, 1985 edition files only six characters without the character set.
+        let field_control_length = match field_control_length_value.trim() {
+            "" if !is_ddr => 0,
+            value => match value.parse::<u8>() {
+                Ok(length) if !is_ddr || [0, 6, 9].contains(&length) => length,
+                _ => {
+                    return Err(ReadError::ParseError(format!(
+                        "Invalid Field Control Length: {}",
+                        field_control_length_value
+                    )))
+                }
+            },
+        };
 
         let base_address = reader.read_u64_str(5)?;
 
         let character_set = reader.read_str(3)?;
-        if (is_ddr && character_set != " ! ") || (!is_ddr && character_set != "   ") {
+        if strict && ((is_ddr && character_set != " ! ") || (!is_ddr && character_set != "   ")) {
             return Err(ReadError::ParseError(format!(
                 "Invalid Extended Character Set Indicator: {}",
                 character_set
@@ -202,8 +266,9 @@ impl Leader {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::zero_prefixed_literal)]
 pub(crate) mod tests {
-    use crate::{leader::Leader, ReadResult, Reader};
+    use crate::{leader::Leader, ReadResult, Reader, ReaderOptions};
     use std::io::{BufReader, Cursor};
 
     pub fn ascii_ddr_leader() -> ReadResult<Leader> {
@@ -211,7 +276,7 @@ pub(crate) mod tests {
         let buffer = Cursor::new(bytes);
         let bufreader = BufReader::new(buffer);
         let mut reader = Reader::new(bufreader);
-        Leader::read_ddr(&mut reader)
+        Leader::read_ddr(&mut reader, &ReaderOptions::strict())
     }
 
     #[test]
@@ -234,11 +299,12 @@ pub(crate) mod tests {
         let bytes = [
             "00197 D     00109   5504".as_bytes(),
             "00088 D     00067   5504".as_bytes(),
+            "00088 R     00067   5504".as_bytes(),
         ];
         let buffer = Cursor::new(bytes[index]);
         let bufreader = BufReader::new(buffer);
         let mut reader = Reader::new(bufreader);
-        Leader::read_dr(&mut reader)
+        Leader::read_dr(&mut reader, &ReaderOptions::strict())
     }
 
     #[test]
@@ -251,6 +317,41 @@ pub(crate) mod tests {
             let target = target.unwrap();
             assert_eq!(target.interchange_level, ' ');
             assert_eq!(target.leader_identifier, 'D');
+            assert!(!target.is_reused());
+        }
+
+        let target = ascii_dr_leader(2).unwrap();
+        assert_eq!(target.leader_identifier, 'R');
+        assert!(target.is_reused());
+    }
+
+    #[test]
+    fn test_permissive_leader() {
+        let values = ["002403LE1 0600031 ! 3404", "002402L  A0600031 ! 3404"];
+        let lengths = [6, 6];
+
+        for (value, length) in values.iter().zip(lengths.iter()) {
+            let mut reader = Reader::new(Cursor::new(value.as_bytes()));
+            assert!(Leader::read_ddr(&mut reader, &ReaderOptions::strict()).is_err());
+
+            let mut reader = Reader::new(Cursor::new(value.as_bytes()));
+            let target = Leader::read_ddr(&mut reader, &ReaderOptions::permissive()).unwrap();
+            assert_eq!(target.field_control_length, *length);
+        }
+
+        let value = "00024 DE1A  00031SDT3404";
+        let mut reader = Reader::new(Cursor::new(value.as_bytes()));
+        assert!(Leader::read_dr(&mut reader, &ReaderOptions::strict()).is_err());
+        let mut reader = Reader::new(Cursor::new(value.as_bytes()));
+        let target = Leader::read_dr(&mut reader, &ReaderOptions::permissive()).unwrap();
+        assert_eq!(target.code_extension, 'E');
+        assert_eq!(target.version_number, '1');
+        assert_eq!(target.application_indicator, 'A');
+        assert_eq!(target.character_set, "SDT");
+
+        for value in &["002403LE1 0700031 ! 3404", "002403LE1 0400031 ! 3404"] {
+            let mut reader = Reader::new(Cursor::new(value.as_bytes()));
+            assert!(Leader::read_ddr(&mut reader, &ReaderOptions::permissive()).is_err());
         }
     }
 }
@@ -7,14 +7,24 @@ pub mod error;
 mod ddf;
 pub use ddf::DataDescriptiveFile;
 
+mod records;
+pub use records::{DataDescriptiveFileReader, Records};
+
+mod view;
+pub use view::{DataDescriptiveFileView, RecordViews};
+
 mod directory;
 
 mod directory_entry;
 
 mod leader;
 
+mod options;
+pub use options::ReaderOptions;
+
 mod reader;
-use reader::{ReadResult, Reader};
+pub use reader::ReadResult;
+use reader::Reader;
 
 /// binary value for ISO8211 field terminator
 const FIELD_TERMINATOR: u8 = 0x1e;
@@ -23,4 +33,5 @@ const FIELD_TERMINATOR: u8 = 0x1e;
 const UNIT_TERMINATOR: u8 = 0x1f;
 
 /// binary value for null
+#[allow(dead_code)]
 const NULL: u8 = 0x00;
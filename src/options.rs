@@ -0,0 +1,28 @@
+/// Options that control how strictly files are read
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct ReaderOptions {
+    strict: bool,
+}
+
+impl ReaderOptions {
+    /// Rejects leaders that deviate from the S-57 profile of ISO 8211
+    pub fn strict() -> ReaderOptions {
+        ReaderOptions { strict: true }
+    }
+
+    /// Accepts the leader values used by other profiles and older editions of ISO 8211, such as
+    /// a field control length of `06`, and records them as found
+    pub fn permissive() -> ReaderOptions {
+        ReaderOptions { strict: false }
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+}
+
+impl Default for ReaderOptions {
+    fn default() -> ReaderOptions {
+        ReaderOptions::strict()
+    }
+}
@@ -1,113 +1,222 @@
 use crate::{error::ReadError, FIELD_TERMINATOR, UNIT_TERMINATOR};
 
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
 use std::result::Result;
 
-/// ISO 8211 file reader
+/// ISO 8211 file reader, looking ahead within its buffer so that any source can be read
+/// without seeking
 pub struct Reader<T> {
     buffer: BufReader<T>,
+    /// the number of bytes consumed so far
+    position: u64,
 }
 
 pub type ReadResult<T> = Result<T, ReadError>;
 
-impl<T: Read + Seek> Reader<T> {
-    pub fn new(buffer: BufReader<T>) -> Reader<T> {
-        Reader { buffer }
+impl<T: Read> Reader<T> {
+    pub fn new(inner: T) -> Reader<T> {
+        Reader {
+            buffer: BufReader::new(inner),
+            position: 0,
+        }
     }
 
-    pub fn is_eof(&mut self) -> ReadResult<bool> {
-        let mut buf: [u8; 1] = [0; 1];
-        let bytes = self.buffer.read(&mut buf)?;
-        if bytes == 0 {
-            Ok(true)
-        } else {
-            self.buffer.seek(SeekFrom::Current(-1))?;
-            Ok(false)
+    /// A reader with the given buffer size, raised to one byte when zero so it can look ahead
+    pub(crate) fn with_capacity(capacity: usize, inner: T) -> Reader<T> {
+        Reader {
+            buffer: BufReader::with_capacity(capacity.max(1), inner),
+            position: 0,
         }
     }
 
+    /// The number of bytes read so far
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn is_eof(&mut self) -> ReadResult<bool> {
+        Ok(self.buffer.fill_buf()?.is_empty())
+    }
+
     pub fn peek_byte(&mut self) -> ReadResult<u8> {
-        let result = self.read_u8();
-        if result.is_ok() {
-            self.buffer.seek(SeekFrom::Current(-1))?;
+        match self.buffer.fill_buf()?.first() {
+            Some(byte) => Ok(*byte),
+            None => Err(ReadError::from(io::Error::from(ErrorKind::UnexpectedEof))),
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> ReadResult<()> {
+        self.buffer.read_exact(buf)?;
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+
+    fn consume(&mut self, length: usize) {
+        self.buffer.consume(length);
+        self.position += length as u64;
+    }
+
+    /// Passes the next `length` bytes to `f`, straight from the buffer unless they straddle
+    /// its end
+    fn with_bytes<R>(
+        &mut self,
+        length: usize,
+        f: impl FnOnce(&[u8]) -> ReadResult<R>,
+    ) -> ReadResult<R> {
+        let buffered = self.buffer.fill_buf()?;
+        if buffered.len() >= length {
+            let result = f(&buffered[..length]);
+            self.consume(length);
+            return result;
+        }
+        let mut buf = vec![0u8; length];
+        self.read_exact(&mut buf)?;
+        f(&buf)
+    }
+
+    /// Reads up to the next terminator, which is consumed but not returned, searching the
+    /// buffer a chunk at a time
+    fn read_until(&mut self, terminator: u8) -> ReadResult<Vec<u8>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let length = self.buffer.read_until(terminator, &mut bytes)?;
+        self.position += length as u64;
+        match bytes.pop() {
+            Some(byte) if byte == terminator => Ok(bytes),
+            _ => Err(ReadError::from(io::Error::from(ErrorKind::UnexpectedEof))),
         }
-        result
     }
 
     pub fn read_char(&mut self) -> ReadResult<char> {
-        let mut buf: [u8; 1] = [0; 1];
-        self.buffer.read_exact(&mut buf)?;
-        Ok(buf[0] as char)
+        Ok(self.read_u8()? as char)
     }
 
     pub fn read_bytes(&mut self, length: usize) -> ReadResult<Vec<u8>> {
         let mut buf: Vec<u8> = vec![0u8; length];
-        self.buffer.read_exact(&mut buf)?;
+        self.read_exact(&mut buf)?;
         Ok(buf)
     }
 
     pub fn read_str(&mut self, length: usize) -> ReadResult<String> {
-        let mut buf = vec![0u8; length];
-        self.buffer.read_exact(&mut buf)?;
+        let buf = self.read_bytes(length)?;
         let s = String::from_utf8(buf)?;
         Ok(s)
     }
 
-    pub fn read_str_ft(&mut self) -> ReadResult<String> {
-        let mut buf: [u8; 1] = [0; 1];
+    /// Reads up to the next field terminator, which is consumed but not returned
+    pub fn read_bytes_ft(&mut self) -> ReadResult<Vec<u8>> {
+        self.read_until(FIELD_TERMINATOR)
+    }
+
+    /// Reads up to the next terminator, which is consumed but not returned. A terminator of
+    /// two bytes, as in UCS-2 text, only ends the data on a whole character.
+    pub(crate) fn read_bytes_until(&mut self, terminator: &[u8]) -> ReadResult<Vec<u8>> {
+        if terminator.len() == 1 {
+            return self.read_until(terminator[0]);
+        }
         let mut bytes: Vec<u8> = Vec::new();
-        while {
-            self.buffer.read_exact(&mut buf)?;
-            buf[0] != FIELD_TERMINATOR
-        } {
-            bytes.push(buf[0]);
+        loop {
+            let unit = self.read_bytes(terminator.len())?;
+            if unit == terminator {
+                return Ok(bytes);
+            }
+            bytes.extend(unit);
         }
-        let s = String::from_utf8(bytes)?;
+    }
+
+    pub fn read_str_ft(&mut self) -> ReadResult<String> {
+        let s = String::from_utf8(self.read_bytes_ft()?)?;
         Ok(s)
     }
 
     pub fn read_str_ut(&mut self) -> ReadResult<String> {
-        let mut buf: [u8; 1] = [0; 1];
-        let mut bytes: Vec<u8> = Vec::new();
-        while {
-            self.buffer.read_exact(&mut buf)?;
-            buf[0] != UNIT_TERMINATOR
-        } {
-            bytes.push(buf[0]);
-        }
-        let s = String::from_utf8(bytes)?;
+        let s = String::from_utf8(self.read_until(UNIT_TERMINATOR)?)?;
         Ok(s)
     }
 
     pub fn read_u8(&mut self) -> ReadResult<u8> {
-        let mut buf = [0u8; 1];
-        self.buffer.read_exact(&mut buf)?;
-        let r = u8::from_le_bytes(buf);
+        let r = self.peek_byte()?;
+        self.consume(1);
         Ok(r)
     }
 
     pub fn read_u8_str(&mut self, length: usize) -> ReadResult<u8> {
-        let mut buf = vec![0u8; length];
-        self.buffer.read_exact(&mut buf)?;
-        let s = String::from_utf8(buf)?;
-        let r = s.parse::<u8>()?;
-        Ok(r)
+        self.with_bytes(length, |bytes| Ok(parse_str(bytes)?.parse::<u8>()?))
     }
 
     pub fn read_u64(&mut self, length: usize) -> ReadResult<u64> {
-        let mut buf = vec![0u8; length];
-        self.buffer.read_exact(&mut buf)?;
-        let mut val = [0u8; 8];
-        val[8 - length..].clone_from_slice(&buf);
-        let r = u64::from_le_bytes(val);
-        Ok(r)
+        self.with_bytes(length, |bytes| {
+            let mut val = [0u8; 8];
+            val[8 - length..].copy_from_slice(bytes);
+            Ok(u64::from_le_bytes(val))
+        })
     }
 
     pub fn read_u64_str(&mut self, length: usize) -> ReadResult<u64> {
-        let mut buf = vec![0u8; length];
-        self.buffer.read_exact(&mut buf)?;
-        let s = String::from_utf8(buf)?;
-        let r = s.parse::<u64>()?;
-        Ok(r)
+        self.with_bytes(length, |bytes| Ok(parse_str(bytes)?.parse::<u64>()?))
+    }
+}
+
+/// Borrows the bytes as a str, with invalid UTF-8 reported as by `String::from_utf8`, which
+/// copies the bytes only when they are invalid
+pub(crate) fn parse_str(bytes: &[u8]) -> ReadResult<&str> {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => Ok(s),
+        Err(_) => Err(ReadError::from(
+            String::from_utf8(bytes.to_vec()).unwrap_err(),
+        )),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{error::ReadError, Reader, FIELD_TERMINATOR, UNIT_TERMINATOR};
+    use std::io::BufReader;
+
+    #[test]
+    fn test_buffer_boundaries() {
+        // a buffer smaller than the values read makes them straddle its end
+        let bytes = [
+            b"00042".as_ref(),
+            b"first unit".as_ref(),
+            &[UNIT_TERMINATOR],
+            b"field".as_ref(),
+            &[FIELD_TERMINATOR],
+            &[0x01, 0x02, 0x03],
+            b"7".as_ref(),
+        ]
+        .concat();
+        let mut reader = Reader::with_capacity(4, &bytes[..]);
+
+        assert_eq!(reader.read_u64_str(5).unwrap(), 42);
+        assert_eq!(reader.peek_byte().unwrap(), b'f');
+        assert_eq!(reader.read_str_ut().unwrap(), "first unit");
+        assert_eq!(reader.read_str_ft().unwrap(), "field");
+        assert_eq!(reader.position(), 22);
+        assert_eq!(reader.read_bytes(3).unwrap(), [0x01, 0x02, 0x03]);
+        assert_eq!(reader.read_u8_str(1).unwrap(), 7);
+        assert!(reader.is_eof().unwrap());
+        assert!(reader.peek_byte().is_err());
+
+        // a terminator that never comes
+        let mut reader = Reader::with_capacity(4, b"no terminator".as_ref());
+        assert!(reader.read_bytes_ft().is_err());
+
+        let mut reader = Reader::new(b"4x".as_ref());
+        assert!(reader.read_u64_str(2).is_err());
+
+        let mut reader = Reader::new(b"4\xff".as_ref());
+        match reader.read_u64_str(2) {
+            Err(ReadError::Utf8Error(_)) => {}
+            e => panic!("{:?}", e),
+        }
+
+        let mut reader = Reader::with_capacity(0, b"7".as_ref());
+        assert!(!reader.is_eof().unwrap());
+        assert_eq!(reader.read_u8_str(1).unwrap(), 7);
+
+        // a source that brings its own empty buffer is still read ahead
+        let mut reader = Reader::new(BufReader::with_capacity(0, b"7".as_ref()));
+        assert!(!reader.is_eof().unwrap());
+        assert_eq!(reader.read_u8_str(1).unwrap(), 7);
     }
 }
@@ -0,0 +1,231 @@
+use crate::{
+    ddr::DataDescriptiveRecord,
+    dr::{DataRecord, Directory, Leader},
+    ReadResult, Reader, ReaderOptions,
+};
+use std::io::Read;
+use std::sync::Arc;
+
+/// A file whose data descriptive record has been read, with its data records still to come
+pub struct DataDescriptiveFileReader<T> {
+    reader: Reader<T>,
+    data_descriptive_record: DataDescriptiveRecord,
+    options: ReaderOptions,
+}
+
+impl<T: Read> DataDescriptiveFileReader<T> {
+    /// Reads the data descriptive record from the start of the reader
+    pub(crate) fn new(
+        mut reader: Reader<T>,
+        options: &ReaderOptions,
+    ) -> ReadResult<DataDescriptiveFileReader<T>> {
+        let data_descriptive_record = DataDescriptiveRecord::read(&mut reader, options)?;
+        Ok(DataDescriptiveFileReader {
+            reader,
+            data_descriptive_record,
+            options: *options,
+        })
+    }
+
+    pub fn data_descriptive_record(&self) -> &DataDescriptiveRecord {
+        &self.data_descriptive_record
+    }
+
+    /// The data records, read one at a time as the iterator advances
+    pub fn records(self) -> Records<T> {
+        Records {
+            records: RecordIter::new(self),
+        }
+    }
+}
+
+/// Iterator over the data records of a file, it stops after the first error
+pub struct Records<T> {
+    records: RecordIter<DataDescriptiveFileReader<T>>,
+}
+
+impl<T: Read> Records<T> {
+    pub fn data_descriptive_record(&self) -> &DataDescriptiveRecord {
+        &self.records.source.data_descriptive_record
+    }
+
+    pub fn into_data_descriptive_record(self) -> DataDescriptiveRecord {
+        self.records.source.data_descriptive_record
+    }
+}
+
+impl<T: Read> Iterator for Records<T> {
+    type Item = ReadResult<DataRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.next()
+    }
+}
+
+impl<T: Read> RecordSource for DataDescriptiveFileReader<T> {
+    type Record = DataRecord;
+
+    fn is_empty(&mut self) -> ReadResult<bool> {
+        self.reader.is_eof()
+    }
+
+    fn read_layout(&mut self) -> ReadResult<(Leader, Directory)> {
+        let leader = Leader::read_dr(&mut self.reader, &self.options)?;
+        let directory = Directory::read(&mut self.reader, &leader)?;
+        directory.skip_to_field_area(&mut self.reader, &leader)?;
+        Ok((leader, directory))
+    }
+
+    fn read_record(&mut self, layout: Arc<(Leader, Directory)>) -> ReadResult<DataRecord> {
+        let length = layout.1.field_area_length(&layout.0)?;
+        let field_area = self.reader.read_bytes(length)?;
+        // only the layout of an 'R' record is shared with the records that follow it
+        let (leader, directory) =
+            Arc::try_unwrap(layout).unwrap_or_else(|layout| (*layout).clone());
+        DataRecord::decode(
+            leader,
+            directory,
+            &field_area,
+            &self.data_descriptive_record,
+        )
+    }
+}
+
+/// Where the data records of a file are read from, a stream or the bytes of a whole file
+pub(crate) trait RecordSource {
+    type Record;
+
+    fn is_empty(&mut self) -> ReadResult<bool>;
+
+    /// Reads the leader and directory of a record, up to the start of its field area
+    fn read_layout(&mut self) -> ReadResult<(Leader, Directory)>;
+
+    /// Reads the field area that comes next, laid out by the given leader and directory
+    fn read_record(&mut self, layout: Arc<(Leader, Directory)>) -> ReadResult<Self::Record>;
+}
+
+/// Reads the data records of a source one at a time, it stops after the first error
+pub(crate) struct RecordIter<S> {
+    pub(crate) source: S,
+    /// the leader and directory of an 'R' record, used by all the records that follow it
+    reused: Option<Arc<(Leader, Directory)>>,
+    finished: bool,
+}
+
+impl<S: RecordSource> RecordIter<S> {
+    pub(crate) fn new(source: S) -> RecordIter<S> {
+        RecordIter {
+            source,
+            reused: None,
+            finished: false,
+        }
+    }
+
+    fn read_next(&mut self) -> ReadResult<Option<S::Record>> {
+        if self.source.is_empty()? {
+            return Ok(None);
+        }
+
+        let layout = match &self.reused {
+            Some(layout) => Arc::clone(layout),
+            None => {
+                let layout = Arc::new(self.source.read_layout()?);
+                if layout.0.is_reused() {
+                    self.reused = Some(Arc::clone(&layout));
+                }
+                layout
+            }
+        };
+        self.source.read_record(layout).map(Some)
+    }
+}
+
+impl<S: RecordSource> Iterator for RecordIter<S> {
+    type Item = ReadResult<S::Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_next() {
+            Ok(Some(data_record)) => Some(Ok(data_record)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{dr::RecordIdentifier, DataDescriptiveFile, FIELD_TERMINATOR, UNIT_TERMINATOR};
+
+    #[test]
+    fn test_reused_leaders() {
+        let bytes = [
+            "001543LE1 0900058 ! 3404".as_bytes(),
+            "0000019000000010350019TEST0420054".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001TEST".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0100;&   Record identifier".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR],
+            "(I(3))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1600;&   Test field".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "NAME!VALU".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A(3),I(2))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00057 D     00047   3404".as_bytes(),
+            "00010040000TEST0060004".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "001".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "one01".as_bytes(),
+            &[FIELD_TERMINATOR],
+            // an 'R' leader and directory, reused by the field areas that follow
+            "00057 R     00047   3404".as_bytes(),
+            "00010040000TEST0060004".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "002".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "two02".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "thr03".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "004".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "fou04".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target = DataDescriptiveFile::open_reader(&bytes[..]).unwrap();
+
+        let data_records: Vec<_> = target.records().map(Result::unwrap).collect();
+        assert_eq!(data_records.len(), 4);
+        assert!(!data_records[0].leader().is_reused());
+        for (index, data_record) in data_records.iter().enumerate() {
+            assert_eq!(
+                data_record.record_identifier(),
+                Some(&RecordIdentifier::Number(index as u64 + 1))
+            );
+            let test = data_record.data_field("TEST").unwrap();
+            assert_eq!(test.get_i64("VALU"), Some(index as i64 + 1));
+        }
+        assert_eq!(
+            data_records[3].data_field("TEST").unwrap().get_str("NAME"),
+            Some("fou")
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{
+    ddr::DataDescriptiveRecord,
+    dr::{DataRecordView, Directory, Leader},
+    error::ReadError,
+    leader::LEADER_LENGTH,
+    records::{RecordIter, RecordSource},
+    ReadResult, Reader, ReaderOptions,
+};
+use std::sync::Arc;
+
+/// A whole file held in memory, such as a memory-mapped file, whose data records are read as
+/// views that borrow from its bytes
+pub struct DataDescriptiveFileView<B> {
+    bytes: B,
+    data_descriptive_record: DataDescriptiveRecord,
+    options: ReaderOptions,
+    /// the position of the first data record
+    start: usize,
+}
+
+impl<B: AsRef<[u8]>> DataDescriptiveFileView<B> {
+    /// Reads the data descriptive record from the start of the bytes
+    pub fn new(bytes: B, options: &ReaderOptions) -> ReadResult<DataDescriptiveFileView<B>> {
+        let mut reader = Reader::new(bytes.as_ref());
+        let data_descriptive_record = DataDescriptiveRecord::read(&mut reader, options)?;
+        let start = reader.position() as usize;
+        Ok(DataDescriptiveFileView {
+            bytes,
+            data_descriptive_record,
+            options: *options,
+            start,
+        })
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+
+    pub fn data_descriptive_record(&self) -> &DataDescriptiveRecord {
+        &self.data_descriptive_record
+    }
+
+    /// The data records, each read as the iterator advances without copying its field area
+    pub fn records(&self) -> RecordViews<'_> {
+        RecordViews {
+            records: RecordIter::new(RecordBytes {
+                bytes: self.bytes.as_ref(),
+                position: self.start,
+                data_descriptive_record: &self.data_descriptive_record,
+                options: self.options,
+            }),
+        }
+    }
+}
+
+/// Iterator over views of the data records of a file, it stops after the first error
+pub struct RecordViews<'a> {
+    records: RecordIter<RecordBytes<'a>>,
+}
+
+impl<'a> Iterator for RecordViews<'a> {
+    type Item = ReadResult<DataRecordView<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.next()
+    }
+}
+
+/// The bytes of the data records that are still to be read
+struct RecordBytes<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    data_descriptive_record: &'a DataDescriptiveRecord,
+    options: ReaderOptions,
+}
+
+impl<'a> RecordBytes<'a> {
+    /// The next `length` bytes, which have to be within the file
+    fn take(&mut self, length: usize) -> ReadResult<&'a [u8]> {
+        let bytes = self.bytes;
+        match bytes.get(self.position..self.position + length) {
+            Some(taken) => {
+                self.position += length;
+                Ok(taken)
+            }
+            None => Err(ReadError::ParseError(format!(
+                "Record at position {} ends after the {} bytes of the file",
+                self.position,
+                bytes.len()
+            ))),
+        }
+    }
+}
+
+impl<'a> RecordSource for RecordBytes<'a> {
+    type Record = DataRecordView<'a>;
+
+    fn is_empty(&mut self) -> ReadResult<bool> {
+        Ok(self.position >= self.bytes.len())
+    }
+
+    fn read_layout(&mut self) -> ReadResult<(Leader, Directory)> {
+        // the leader and directory are small, a buffer the size of the leader is enough to
+        // look ahead while reading them
+        let remaining = &self.bytes[self.position..];
+        let mut reader = Reader::with_capacity(LEADER_LENGTH, remaining);
+        let leader = Leader::read_dr(&mut reader, &self.options)?;
+        let directory = Directory::read(&mut reader, &leader)?;
+        let offset = directory.field_area_offset(&leader)?;
+        self.take(offset)?;
+        Ok((leader, directory))
+    }
+
+    fn read_record(&mut self, layout: Arc<(Leader, Directory)>) -> ReadResult<DataRecordView<'a>> {
+        let length = layout.1.field_area_length(&layout.0)?;
+        let field_area = self.take(length)?;
+        DataRecordView::new(layout, field_area, self.data_descriptive_record)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        dr::{SubfieldRef, SubfieldValue},
+        DataDescriptiveFile, DataDescriptiveFileView, ReaderOptions, FIELD_TERMINATOR,
+        UNIT_TERMINATOR,
+    };
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_record_views() {
+        let bytes = [
+            "001503LE1 0900058 ! 3404".as_bytes(),
+            "0000019000000010350019TEST0380054".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0000;&   ".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "0001TEST".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "0100;&   Record identifier".as_bytes(),
+            &[UNIT_TERMINATOR, UNIT_TERMINATOR],
+            "(I(3))".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "1600;&-A Test field".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "NAME!VALU".as_bytes(),
+            &[UNIT_TERMINATOR],
+            "(A,b12)".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "00058 D     00047   3404".as_bytes(),
+            "00010040000TEST0070004".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "001".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "one".as_bytes(),
+            &[UNIT_TERMINATOR, 0x01, 0x00, FIELD_TERMINATOR],
+            // an 'R' leader and directory, reused by the field area that follows
+            "00058 R     00047   3404".as_bytes(),
+            "00010040000TEST0070004".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "002".as_bytes(),
+            &[FIELD_TERMINATOR],
+            b"\xe9t\xe9",
+            &[UNIT_TERMINATOR, 0x02, 0x00, FIELD_TERMINATOR],
+            "003".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "six".as_bytes(),
+            &[UNIT_TERMINATOR, 0x03, 0x00, FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target = DataDescriptiveFileView::new(&bytes[..], &ReaderOptions::default()).unwrap();
+        let eager = DataDescriptiveFile::from_bytes(&bytes).unwrap();
+
+        let views: Vec<_> = target.records().map(Result::unwrap).collect();
+        assert_eq!(views.len(), 3);
+        // the records after an 'R' record share its leader and directory
+        assert!(views[1].leader().is_reused());
+        assert!(std::ptr::eq(views[1].leader(), views[2].leader()));
+        for (view, expected) in views.iter().zip(eager.data_records()) {
+            let values: Vec<SubfieldValue> = view
+                .data_field("TEST")
+                .unwrap()
+                .values()
+                .unwrap()
+                .into_iter()
+                .map(SubfieldRef::into_owned)
+                .collect();
+            assert_eq!(&values, expected.data_field("TEST").unwrap().values());
+            assert_eq!(view.record_identifier(), expected.record_identifier());
+            assert_eq!(
+                view.to_data_record().unwrap().record_identifier(),
+                expected.record_identifier()
+            );
+        }
+
+        // text that is already valid UTF-8 is borrowed from the file
+        let names: Vec<SubfieldRef> = views
+            .iter()
+            .map(|view| view.data_field("TEST").unwrap().values().unwrap()[0].clone())
+            .collect();
+        match &names[0] {
+            SubfieldRef::String(Cow::Borrowed(name)) => assert_eq!(*name, "one"),
+            name => panic!("{:?}", name),
+        }
+        match &names[1] {
+            SubfieldRef::String(Cow::Owned(name)) => assert_eq!(name, "été"),
+            name => panic!("{:?}", name),
+        }
+        match &names[2] {
+            SubfieldRef::String(Cow::Borrowed(name)) => assert_eq!(*name, "six"),
+            name => panic!("{:?}", name),
+        }
+        assert_eq!(
+            views[2].data_field("TEST").unwrap().data(),
+            &bytes[bytes.len() - 7..bytes.len() - 1]
+        );
+
+        // a record cut short ends the views with an error
+        let truncated = &bytes[..bytes.len() - 2];
+        let target = DataDescriptiveFileView::new(truncated, &ReaderOptions::default()).unwrap();
+        let views: Vec<_> = target.records().collect();
+        assert_eq!(views.len(), 3);
+        assert!(views[2].is_err());
+
+        // fields out of the order of the field tree are rejected, as when reading by copying
+        let misplaced = [
+            &bytes[..target.start],
+            "00058 D     00047   3404".as_bytes(),
+            "TEST007000000010040007".as_bytes(),
+            &[FIELD_TERMINATOR],
+            "one".as_bytes(),
+            &[UNIT_TERMINATOR, 0x01, 0x00, FIELD_TERMINATOR],
+            "001".as_bytes(),
+            &[FIELD_TERMINATOR],
+        ]
+        .concat();
+        let target =
+            DataDescriptiveFileView::new(&misplaced[..], &ReaderOptions::default()).unwrap();
+        assert!(DataDescriptiveFile::from_bytes(&misplaced).is_err());
+        assert!(target.records().next().unwrap().is_err());
+    }
+}
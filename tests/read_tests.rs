@@ -1,9 +1,14 @@
-use iso8211::DataDescriptiveFile;
+use iso8211::{
+    ddr::{DataStructure, DataType, LexicalLevel},
+    dr::{RecordIdentifier, SubfieldRef, SubfieldValue},
+    DataDescriptiveFile, DataDescriptiveFileView, ReaderOptions,
+};
+use std::io::{Cursor, Read};
 
 pub fn assert_read(path: &str) -> DataDescriptiveFile {
     match DataDescriptiveFile::read(path) {
         Ok(d) => d,
-        Err(e) => panic!(e),
+        Err(e) => panic!("{:?}", e),
     }
 }
 
@@ -23,3 +28,276 @@ fn read_s_64__2_1_1() {
         assert_read(f);
     }
 }
+
+#[test]
+fn read_data_fields() {
+    let ddf = assert_read("tests/s_64/2_1_1/GB5X02SE.000");
+    let data_records = ddf.data_records();
+    assert_eq!(data_records.len(), 74);
+
+    let data_fields = data_records[0].data_fields();
+    assert_eq!(data_fields.len(), 3);
+    assert_eq!(data_fields[0].tag(), "0001");
+    assert_eq!(
+        data_fields[0].values(),
+        &vec![SubfieldValue::UnsignedInteger(1)]
+    );
+    assert_eq!(data_fields[1].tag(), "DSID");
+    assert_eq!(data_fields[1].get_str("DSNM"), Some("GB5X02SE.000"));
+    assert_eq!(data_fields[1].get_u8("RCNM"), Some(10));
+
+    let frid = data_records
+        .iter()
+        .find_map(|dr| dr.data_field("FRID"))
+        .unwrap();
+    assert_eq!(frid.get_u8("RCNM"), Some(100));
+    assert!(frid.get_u16("OBJL").is_some());
+}
+
+#[test]
+fn read_repeating_data_fields() {
+    let ddf = assert_read("tests/s_64/2_1_1/GB5X02SE.000");
+
+    for data_record in ddf.data_records() {
+        for data_field in data_record.data_fields() {
+            match data_field.tag().as_str() {
+                "SG2D" => {
+                    let array = data_field.array().unwrap();
+                    assert_eq!(array.shape(), &[data_field.row_count(), 2]);
+                    for row in data_field.rows() {
+                        assert!(row.get_i32("YCOO").is_some());
+                        assert!(row.get_i32("XCOO").is_some());
+                    }
+                }
+                "FSPT" => {
+                    assert!(data_field.row_count() > 0);
+                    for row in data_field.rows() {
+                        assert_eq!(row.get_bytes("NAME").map(|name| name.len()), Some(5));
+                        assert!(row.get_u8("ORNT").is_some());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn read_schema() {
+    let ddf = assert_read("tests/s_64/2_1_1/GB5X02SE.000");
+    let ddr = ddf.data_descriptive_record();
+
+    let tags = ddr.tags();
+    assert_eq!(tags.len(), 19);
+    assert_eq!(tags[0], "0001");
+    assert!(tags.iter().any(|tag| *tag == "SG2D"));
+    assert!(ddr.data_descriptive_field("XXXX").is_none());
+
+    let sg2d = ddr.data_descriptive_field("SG2D").unwrap();
+    assert_eq!(sg2d.tag(), "SG2D");
+    assert_eq!(sg2d.field_name(), "2-D coordinate field");
+    let field_controls = sg2d.field_controls();
+    assert_eq!(
+        field_controls.data_structure(),
+        &DataStructure::MultiDimensionalStructure
+    );
+    assert_eq!(field_controls.data_type(), &DataType::Binary);
+    assert_eq!(field_controls.escape_sequence(), &LexicalLevel::Level0);
+    assert_eq!(sg2d.array_descriptor().to_string(), "*YCOO!XCOO");
+    assert_eq!(sg2d.format_controls().to_string(), "(2b24)");
+
+    let attf = ddr.data_descriptive_field("ATTF").unwrap();
+    assert_eq!(
+        attf.field_controls().escape_sequence(),
+        &LexicalLevel::Level1
+    );
+    assert_eq!(attf.field_controls().data_type(), &DataType::Mixed);
+
+    let leader = ddr.leader();
+    assert_eq!(leader.interchange_level(), &'3');
+    assert_eq!(leader.leader_identifier(), &'L');
+    assert_eq!(leader.character_set(), " ! ");
+    assert_eq!(ddr.file_control_field().tag_pairs().len(), 18);
+}
+
+#[test]
+fn read_field_tree() {
+    let ddf = assert_read("tests/s_64/2_1_1/GB5X02SE.000");
+
+    let field_tree = ddf.data_descriptive_record().field_tree();
+    assert_eq!(field_tree.parent("ATTF"), Some("FRID"));
+    assert_eq!(field_tree.parent("SG2D"), Some("VRID"));
+
+    let data_record = ddf
+        .data_records()
+        .iter()
+        .find(|dr| dr.data_field("ATTF").is_some())
+        .unwrap();
+    let roots = data_record.roots();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].field().tag(), "0001");
+
+    let frid = roots[0].child("FRID").unwrap();
+    assert_eq!(frid.parent().unwrap().field().tag(), "0001");
+    assert!(frid.child("ATTF").is_some());
+    assert!(frid
+        .children()
+        .iter()
+        .all(|node| node.field().tag() != "0001"));
+    assert_eq!(
+        roots[0].descendants().len(),
+        data_record.data_fields().len() - 1
+    );
+
+    let attf = data_record.find_node("ATTF").unwrap();
+    assert_eq!(attf.parent().unwrap().index(), frid.index());
+}
+
+#[test]
+fn read_record_identifiers() {
+    let ddf = assert_read("tests/s_64/2_1_1/GB5X02SE.000");
+
+    let data_records = ddf.data_records();
+    for (index, data_record) in data_records.iter().enumerate() {
+        assert_eq!(
+            data_record.record_identifier(),
+            Some(&RecordIdentifier::Number(index as u64 + 1))
+        );
+    }
+    let record_identifier = RecordIdentifier::Number(3);
+    assert!(std::ptr::eq(
+        ddf.data_record(&record_identifier).unwrap(),
+        &data_records[2]
+    ));
+    assert!(ddf
+        .data_descriptive_record()
+        .user_augmented_file_description()
+        .is_none());
+}
+
+#[test]
+fn stream_records() {
+    let path = "tests/s_64/2_1_1/GB5X02SE.000";
+    let file = DataDescriptiveFile::open(path).unwrap();
+    assert_eq!(
+        file.data_descriptive_record()
+            .data_descriptive_fields()
+            .len(),
+        19
+    );
+
+    let eager = assert_read(path);
+    let mut count = 0;
+    for (data_record, expected) in file.records().zip(eager.data_records()) {
+        let data_record = data_record.unwrap();
+        assert_eq!(
+            data_record.record_identifier(),
+            expected.record_identifier()
+        );
+        assert_eq!(
+            data_record.data_fields().len(),
+            expected.data_fields().len()
+        );
+        count += 1;
+    }
+    assert_eq!(count, 74);
+
+    // a truncated file yields its complete records, then an error and nothing more
+    let bytes = std::fs::read(path).unwrap();
+    let truncated = Cursor::new(&bytes[..bytes.len() - 10]);
+    let records: Vec<_> = DataDescriptiveFile::open_reader(truncated)
+        .unwrap()
+        .records()
+        .collect();
+    assert_eq!(records.len(), 74);
+    assert!(records[..73].iter().all(Result::is_ok));
+    assert!(records[73].is_err());
+}
+
+/// Compares the views of each data record with the records read by copying
+fn assert_views<B: AsRef<[u8]>>(file: &DataDescriptiveFileView<B>, eager: &DataDescriptiveFile) {
+    let mut count = 0;
+    for (view, expected) in file.records().zip(eager.data_records()) {
+        let view = view.unwrap();
+        assert_eq!(view.data_fields().len(), expected.data_fields().len());
+        for (field, expected) in view.data_fields().iter().zip(expected.data_fields()) {
+            assert_eq!(field.tag(), expected.tag());
+            let values: Vec<SubfieldValue> = field
+                .values()
+                .unwrap()
+                .into_iter()
+                .map(SubfieldRef::into_owned)
+                .collect();
+            assert_eq!(&values, expected.values());
+        }
+        assert_eq!(
+            view.to_data_record().unwrap().record_identifier(),
+            expected.record_identifier()
+        );
+        count += 1;
+    }
+    assert_eq!(count, eager.data_records().len());
+}
+
+#[test]
+fn view_records() {
+    let path = "tests/s_64/2_1_1/GB5X01SW.000";
+    let bytes = std::fs::read(path).unwrap();
+    let file = DataDescriptiveFileView::new(&bytes[..], &ReaderOptions::default()).unwrap();
+    assert_views(&file, &assert_read(path));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn map_records() {
+    let path = "tests/s_64/2_1_1/GB5X02SE.000";
+    // the sample files are not changed while the tests run
+    let file = unsafe { DataDescriptiveFile::map(path) }.unwrap();
+    assert_eq!(file.bytes(), &std::fs::read(path).unwrap()[..]);
+    assert_views(&file, &assert_read(path));
+}
+
+/// A source that can not seek and hands out a few bytes at a time, like a pipe
+struct Trickle<'a> {
+    bytes: &'a [u8],
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = buf.len().min(self.bytes.len()).min(3);
+        buf[..length].copy_from_slice(&self.bytes[..length]);
+        self.bytes = &self.bytes[length..];
+        Ok(length)
+    }
+}
+
+#[test]
+fn read_non_seekable_streams() {
+    let path = "tests/s_64/2_1_1/GB5X01NE.000";
+    let bytes = std::fs::read(path).unwrap();
+    let eager = assert_read(path);
+
+    let file = DataDescriptiveFile::from_reader(Trickle { bytes: &bytes }).unwrap();
+    assert_eq!(file.data_records().len(), eager.data_records().len());
+
+    let chained = Cursor::new(&bytes[..100]).chain(Cursor::new(&bytes[100..]));
+    let records = DataDescriptiveFile::open_reader(chained).unwrap().records();
+    let identifiers: Vec<_> = records
+        .map(|data_record| data_record.unwrap().record_identifier().cloned())
+        .collect();
+    let expected: Vec<_> = eager
+        .data_records()
+        .iter()
+        .map(|data_record| data_record.record_identifier().cloned())
+        .collect();
+    assert_eq!(identifiers, expected);
+
+    let truncated = Trickle {
+        bytes: &bytes[..bytes.len() - 1],
+    };
+    let records: Vec<_> = DataDescriptiveFile::open_reader(truncated)
+        .unwrap()
+        .records()
+        .collect();
+    assert!(records.last().unwrap().is_err());
+}